@@ -0,0 +1,19 @@
+//! Support code for `try_or_abort!`.
+
+use std::any::Any;
+use std::process;
+
+use crate::chain::chain;
+
+/// Prints a report about a panic which escaped a `try_or_abort!` block at
+/// `location`, including its causes, then aborts the process.
+pub fn abort(payload: Box<dyn Any + Send>, location: &str) -> ! {
+    let mut links = chain(&*payload);
+    if let Some(link) = links.next() {
+        eprintln!("panic escaped try_or_abort! at {}: {}", location, link);
+    }
+    for link in links {
+        eprintln!("caused by: {}", link);
+    }
+    process::abort()
+}
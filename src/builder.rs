@@ -0,0 +1,268 @@
+//! A non-macro interface with the same semantics as `rsexcept!`.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+
+use crate::finally::finally;
+use crate::hook::silence;
+use crate::payload::{downcast_ref, panic_message};
+
+type Payload = Box<dyn Any + Send>;
+type Arm<'a, R> = Box<dyn Fn(Payload) -> Result<R, Payload> + 'a>;
+
+/// An ordered list of catch handlers.
+///
+/// The first handler whose type matches the payload wins. A `Catch` can be
+/// built once and shared between any number of [`Try`]s.
+/// # Examples
+/// ```
+/// use rsexcept::{Catch, Try};
+/// let handlers = Catch::new()
+///     .catch::<i32, _>(|n| n.to_string())
+///     .catch_message(|s| s.to_uppercase());
+/// let a = Try::new(|| std::panic::panic_any(5)).handlers(&handlers).run();
+/// let b = Try::new(|| panic!("oops")).handlers(&handlers).run();
+/// assert_eq!(a, "5");
+/// assert_eq!(b, "OOPS");
+/// ```
+pub struct Catch<'a, R> {
+    arms: Vec<Arm<'a, R>>,
+}
+
+impl<'a, R> Catch<'a, R> {
+    /// Creates an empty handler list.
+    pub fn new() -> Self {
+        Catch { arms: Vec::new() }
+    }
+
+    /// Adds a handler for payloads of type `T`, including a `T` thrown with
+    /// `throw!`.
+    pub fn catch<T: Any, F: Fn(&T) -> R + 'a>(mut self, handler: F) -> Self {
+        self.arms.push(Box::new(move |payload: Payload| {
+            match downcast_ref::<T>(&*payload) {
+                Some(v) => Ok(handler(v)),
+                None => Err(payload),
+            }
+        }));
+        self
+    }
+
+    /// Adds a handler for `panic!` messages, whether they are a
+    /// `&'static str` or a `String`.
+    pub fn catch_message<F: Fn(&str) -> R + 'a>(mut self, handler: F) -> Self {
+        self.arms.push(Box::new(move |payload: Payload| {
+            match panic_message(&*payload) {
+                Some(s) => Ok(handler(s)),
+                None => Err(payload),
+            }
+        }));
+        self
+    }
+
+    /// Adds a handler which matches any payload and takes ownership of it.
+    pub fn catch_all<F: Fn(Payload) -> R + 'a>(mut self, handler: F) -> Self {
+        self.arms
+            .push(Box::new(move |payload| Ok(handler(payload))));
+        self
+    }
+
+    /// Passes `payload` to the first matching handler. Returns the payload
+    /// back if no handler matches.
+    pub fn handle(&self, mut payload: Payload) -> Result<R, Payload> {
+        for arm in &self.arms {
+            payload = match arm(payload) {
+                Ok(v) => return Ok(v),
+                Err(payload) => payload,
+            };
+        }
+        Err(payload)
+    }
+}
+
+impl<'a, R> Default for Catch<'a, R> {
+    fn default() -> Self {
+        Catch::new()
+    }
+}
+
+enum Handlers<'a, R> {
+    Owned(Catch<'a, R>),
+    Shared(&'a Catch<'a, R>),
+}
+
+/// A try block built in code rather than with `rsexcept!`.
+///
+/// Handlers are tried in the order they were added, whether they were added
+/// one by one or as a shared [`Catch`] list. Unmatched payloads are
+/// re-raised, and panics inside the body are silenced the same way they are
+/// inside a `rsexcept!` try block.
+/// # Examples
+/// ```
+/// use rsexcept::Try;
+/// let mut cleaned_up = false;
+/// let res = Try::new(|| -> i32 { std::panic::panic_any(2.5f64) })
+///     .catch::<i32, _>(|n| *n)
+///     .catch::<f64, _>(|f| *f as i32)
+///     .finally(|| cleaned_up = true)
+///     .run();
+/// assert_eq!(res, 2);
+/// assert!(cleaned_up);
+/// ```
+pub struct Try<'a, F, R> {
+    body: F,
+    handlers: Vec<Handlers<'a, R>>,
+    finally: Option<Box<dyn FnOnce() + 'a>>,
+}
+
+impl<'a, F: FnOnce() -> R + UnwindSafe, R> Try<'a, F, R> {
+    /// Creates a try block which runs `body`.
+    pub fn new(body: F) -> Self {
+        Try {
+            body,
+            handlers: Vec::new(),
+            finally: None,
+        }
+    }
+
+    fn owned(&mut self) -> &mut Catch<'a, R> {
+        if !matches!(self.handlers.last(), Some(Handlers::Owned(_))) {
+            self.handlers.push(Handlers::Owned(Catch::new()));
+        }
+        match self.handlers.last_mut() {
+            Some(Handlers::Owned(catch)) => catch,
+            _ => unreachable!(),
+        }
+    }
+
+    fn push(mut self, add: impl FnOnce(Catch<'a, R>) -> Catch<'a, R>) -> Self {
+        let catch = self.owned();
+        *catch = add(std::mem::take(catch));
+        self
+    }
+
+    /// Adds a handler for payloads of type `T`. See [`Catch::catch`].
+    pub fn catch<T: Any, H: Fn(&T) -> R + 'a>(self, handler: H) -> Self {
+        self.push(|catch| catch.catch(handler))
+    }
+
+    /// Adds a handler for `panic!` messages. See [`Catch::catch_message`].
+    pub fn catch_message<H: Fn(&str) -> R + 'a>(self, handler: H) -> Self {
+        self.push(|catch| catch.catch_message(handler))
+    }
+
+    /// Adds a catch-all handler. See [`Catch::catch_all`].
+    pub fn catch_all<H: Fn(Payload) -> R + 'a>(self, handler: H) -> Self {
+        self.push(|catch| catch.catch_all(handler))
+    }
+
+    /// Adds every handler of a shared list.
+    pub fn handlers(mut self, catch: &'a Catch<'a, R>) -> Self {
+        self.handlers.push(Handlers::Shared(catch));
+        self
+    }
+
+    /// Sets a block which always runs last, with the same semantics as a
+    /// `rsexcept!` finally block.
+    pub fn finally<C: FnOnce() + 'a>(mut self, cleanup: C) -> Self {
+        self.finally = Some(Box::new(cleanup));
+        self
+    }
+
+    /// Runs the body, then the matching handler if it panicked, then the
+    /// finally block.
+    pub fn run(self) -> R {
+        let Try {
+            body,
+            handlers,
+            finally: cleanup,
+        } = self;
+        let try_catch = move || {
+            let result = {
+                let _silence = silence();
+                panic::catch_unwind(body)
+            };
+            result.unwrap_or_else(|mut payload| {
+                for handlers in &handlers {
+                    let catch = match handlers {
+                        Handlers::Owned(catch) => catch,
+                        Handlers::Shared(catch) => *catch,
+                    };
+                    payload = match catch.handle(payload) {
+                        Ok(v) => return v,
+                        Err(payload) => payload,
+                    };
+                }
+                panic::resume_unwind(payload)
+            })
+        };
+        match cleanup {
+            Some(cleanup) => finally(panic::catch_unwind(AssertUnwindSafe(try_catch)), cleanup),
+            None => try_catch(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Catch, Try};
+    use crate::tests::raising;
+    use std::panic::{self, panic_any, AssertUnwindSafe};
+
+    #[test]
+    fn no_panic() {
+        let res = Try::new(|| 86).catch::<i32, _>(|i| i + 12).run();
+        assert_eq!(res, 86);
+    }
+    #[test]
+    fn first_match_wins() {
+        let res = Try::new(|| panic_any(6.54))
+            .catch::<i32, _>(|_| panic!("Nope!"))
+            .catch::<f64, _>(|_| 42)
+            .catch_all(|_| 87)
+            .run();
+        assert_eq!(res, 42);
+    }
+    #[test]
+    fn unmatched_reraised() {
+        let mut ran = false;
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            Try::new(|| panic_any(5u8))
+                .catch::<i32, _>(|_| ())
+                .finally(|| ran = true)
+                .run()
+        }));
+        assert_eq!(res.unwrap_err().downcast_ref::<u8>(), Some(&5));
+        assert!(ran);
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn shared_handlers() {
+        let handlers = Catch::new()
+            .catch::<i32, _>(|n| n.to_string())
+            .catch_message(|s| s.to_string());
+        let run = |payload| {
+            Try::new(raising(payload))
+                .catch::<u8, _>(|_| "local".to_string())
+                .handlers(&handlers)
+                .catch_all(|_| "fallback".to_string())
+                .run()
+        };
+        assert_eq!(run(Box::new(1i32)), "1");
+        assert_eq!(run(Box::new(String::from("two"))), "two");
+        assert_eq!(run(Box::new(3u8)), "local");
+        assert_eq!(run(Box::new(4.0f32)), "fallback");
+    }
+    #[test]
+    fn handle_returns_payload() {
+        let handlers: Catch<()> = Catch::new().catch::<i32, _>(|_| ());
+        let payload = handlers.handle(Box::new("nope")).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"nope"));
+    }
+    #[test]
+    fn catches_thrown() {
+        let res = Try::new(|| crate::throw!(7u32))
+            .catch::<u32, _>(|n| *n * 2)
+            .run();
+        assert_eq!(res, 14);
+    }
+}
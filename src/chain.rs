@@ -0,0 +1,139 @@
+//! Walking the chain of causes recorded by `throw_from!`, and the report
+//! printed for uncaught thrown values.
+
+use std::any::Any;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::FusedIterator;
+
+use crate::exception::downcast_exception;
+use crate::group::ExceptionGroup;
+use crate::payload::{downcast_ref, panic_message};
+use crate::thrown::Thrown;
+
+/// Returns an iterator over `payload` followed by its causes, outermost
+/// first.
+/// # Examples
+/// ```
+/// use rsexcept::{chain, rsexcept, throw_from};
+/// let links = rsexcept! {
+///     try {
+///         rsexcept! {
+///             try {
+///                 panic!("disk full")
+///             }
+///             catch {
+///                 _, e => throw_from!("could not save", e)
+///             }
+///         }
+///     }
+///     catch {
+///         _, e => chain(&*e).map(|link| link.to_string()).collect::<Vec<_>>()
+///     }
+/// };
+/// assert_eq!(links, ["&str: could not save", "disk full"]);
+/// ```
+pub fn chain(payload: &(dyn Any + Send)) -> Chain<'_> {
+    Chain {
+        next: Some(payload),
+    }
+}
+
+/// Iterator returned by [`chain`].
+#[derive(Clone)]
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Any + Send)>,
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = Link<'a>;
+
+    fn next(&mut self) -> Option<Link<'a>> {
+        let payload = self.next?;
+        let link = Link::new(payload);
+        self.next = link.thrown().and_then(Thrown::cause);
+        Some(link)
+    }
+}
+
+impl FusedIterator for Chain<'_> {}
+
+/// One payload in a chain of causes.
+#[derive(Clone, Copy)]
+pub struct Link<'a> {
+    payload: &'a (dyn Any + Send),
+}
+
+impl<'a> Link<'a> {
+    pub(crate) fn new(payload: &'a (dyn Any + Send)) -> Self {
+        Link { payload }
+    }
+
+    /// Returns the payload as it was raised.
+    pub fn payload(&self) -> &'a (dyn Any + Send) {
+        self.payload
+    }
+
+    /// Returns the payload, or the value inside its [`Thrown`] envelope, if
+    /// it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&'a T> {
+        downcast_ref(self.payload)
+    }
+
+    /// Returns the envelope if the payload was thrown with `throw!`.
+    pub fn thrown(&self) -> Option<&'a Thrown> {
+        self.payload.downcast_ref()
+    }
+}
+
+/// Formats the link as `type: message` if it was thrown with `throw!`, and
+/// as its message otherwise.
+impl fmt::Display for Link<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = downcast_exception(self.payload)
+            .map(|e| e.message())
+            .or_else(|| panic_message(self.payload).map(String::from))
+            .or_else(|| {
+                self.downcast_ref::<ExceptionGroup>()
+                    .map(|group| format!("group of {} exceptions", group.len()))
+            });
+        match (self.thrown(), message) {
+            (Some(thrown), Some(message)) => write!(f, "{}: {}", thrown.type_name(), message),
+            (Some(thrown), None) => f.write_str(thrown.type_name()),
+            (None, Some(message)) => f.write_str(&message),
+            (None, None) => f.write_str("Box<dyn Any>"),
+        }
+    }
+}
+
+impl fmt::Debug for Link<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Link")
+            .field(&format_args!("{}", self))
+            .finish()
+    }
+}
+
+/// Writes the report for an uncaught panic with `payload`, listing every
+/// cause, every member of a group and every suppressed panic. It follows
+/// the output of the original panic hook, so it has no header of its own.
+pub fn report(out: &mut dyn Write, payload: &(dyn Any + Send)) -> io::Result<()> {
+    for (i, link) in chain(payload).enumerate() {
+        if i > 0 {
+            write!(out, "caused by: ")?;
+        }
+        match link.thrown() {
+            Some(thrown) if i > 0 => writeln!(out, "{} (at {})", link, thrown.location())?,
+            _ => writeln!(out, "{}", link)?,
+        }
+        if let Some(group) = link.downcast_ref::<ExceptionGroup>() {
+            for member in group.members() {
+                writeln!(out, "  member: {}", Link::new(&**member))?;
+            }
+        }
+        for payload in link.thrown().map_or(&[][..], Thrown::suppressed) {
+            writeln!(out, "  suppressed: {}", Link::new(&**payload))?;
+        }
+    }
+    Ok(())
+}
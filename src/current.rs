@@ -0,0 +1,182 @@
+//! The stack of exceptions currently being handled, and support code for
+//! `rethrow!()`.
+//!
+//! Catch arms borrow the payload, so `rethrow!()` can't take it while the
+//! arm is running. Instead it unwinds with a marker; the [`Handling`] guard
+//! of the arm parks the payload on its way out, and the block's catch
+//! boundary swaps the marker for the payload with [`resolve`] once the arm
+//! is gone.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic;
+use std::thread;
+
+type Payload = Box<dyn Any + Send>;
+
+struct Entry {
+    /// The payload, unless an arm took it by value or borrows it mutably.
+    payload: Option<*const (dyn Any + Send)>,
+    /// Whether the payload is still there for `rethrow!()`, i.e. no arm
+    /// took it by value.
+    owned: bool,
+    rethrow: bool,
+}
+
+thread_local! {
+    static STACK: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) };
+    /// The payload of the arm which called `rethrow!()`, while the marker
+    /// travels to the block's catch boundary.
+    static PENDING: RefCell<Option<Payload>> = const { RefCell::new(None) };
+}
+
+/// Passes the payload of the exception the current thread is handling to
+/// `f`, or `None` if it isn't inside a catch arm of `rsexcept!`. Nested
+/// try/catch blocks inside an arm have their own current exception while
+/// their arms run.
+///
+/// Arms which take the payload by value (`move` arms and the catch-all
+/// arm) own it, and `mut` arms borrow it mutably, so there is no current
+/// exception while they run.
+/// # Examples
+/// ```
+/// use rsexcept::{current_exception, rsexcept};
+/// let name = rsexcept! {
+///     try {
+///         std::panic::panic_any(3u8)
+///     }
+///     catch {
+///         u8, _ => current_exception(|e| e.map(|e| e.is::<u8>()))
+///     }
+/// };
+/// assert_eq!(name, Some(true));
+/// assert!(!current_exception(|e| e.is_some()));
+/// ```
+pub fn current_exception<R, F: FnOnce(Option<&(dyn Any + Send)>) -> R>(f: F) -> R {
+    let payload = STACK.with(|stack| stack.borrow().last().and_then(|entry| entry.payload));
+    // SAFETY: the pointer is cleared before the `Handling` guard gives up
+    // the box, and the guard outlives every call made from its arm.
+    f(payload.map(|payload| unsafe { &*payload }))
+}
+
+/// Owns the payload while the catch arms of a block run, and makes it the
+/// current exception.
+pub struct Handling {
+    payload: Option<Payload>,
+    index: usize,
+}
+
+impl Handling {
+    /// A `rethrow!()` from an arm of an enclosing block passes through the
+    /// block unhandled, since its payload is still borrowed by that arm.
+    pub fn new(payload: Payload) -> Self {
+        if payload.is::<Rethrow>() {
+            panic::resume_unwind(payload)
+        }
+        let index = STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            stack.push(Entry {
+                payload: None,
+                owned: false,
+                rethrow: false,
+            });
+            stack.len() - 1
+        });
+        let mut handling = Handling {
+            payload: None,
+            index,
+        };
+        handling.put(payload);
+        handling
+    }
+
+    /// Borrows the payload.
+    pub fn payload(&self) -> &(dyn Any + Send) {
+        let payload = self
+            .payload
+            .as_deref()
+            .expect("payload taken by an earlier arm");
+        // An earlier `mut` arm may have cleared it.
+        self.set(Some(payload as *const _));
+        payload
+    }
+
+    /// Borrows the payload mutably. It isn't the current exception while it
+    /// is borrowed this way.
+    pub fn payload_mut(&mut self) -> &mut (dyn Any + Send) {
+        self.set(None);
+        self.payload
+            .as_deref_mut()
+            .expect("payload taken by an earlier arm")
+    }
+
+    /// Takes the payload by value. It stops being the current exception.
+    pub fn take(&mut self) -> Payload {
+        let payload = self
+            .payload
+            .take()
+            .expect("payload taken by an earlier arm");
+        self.set(None);
+        payload
+    }
+
+    /// Puts back a payload which an arm took but didn't handle.
+    pub fn put(&mut self, payload: Payload) {
+        self.payload = Some(payload);
+        self.payload();
+    }
+
+    fn set(&self, payload: Option<*const (dyn Any + Send)>) {
+        let owned = self.payload.is_some();
+        STACK.with(|stack| {
+            let entry = &mut stack.borrow_mut()[self.index];
+            entry.payload = payload;
+            entry.owned = owned;
+        });
+    }
+}
+
+impl Drop for Handling {
+    fn drop(&mut self) {
+        let entry = STACK.try_with(|stack| stack.borrow_mut().drain(self.index..).next());
+        if let Ok(Some(Entry { rethrow: true, .. })) = entry {
+            if thread::panicking() {
+                let _ = PENDING.try_with(|pending| *pending.borrow_mut() = self.payload.take());
+            }
+        }
+    }
+}
+
+/// The payload `rethrow!()` unwinds with.
+struct Rethrow;
+
+/// Re-raises the exception the innermost catch arm is handling, with its
+/// original payload.
+pub fn rethrow() -> ! {
+    let owned = STACK.with(|stack| match stack.borrow_mut().last_mut() {
+        Some(entry) if entry.owned => {
+            entry.rethrow = true;
+            true
+        }
+        _ => false,
+    });
+    if owned {
+        panic::resume_unwind(Box::new(Rethrow))
+    }
+    panic!("rethrow!() must be used in a catch arm of `rsexcept!` which borrows the payload")
+}
+
+/// Swaps the marker unwound by `rethrow!()` for the payload it stands for.
+pub fn resolve(payload: Payload) -> Payload {
+    if payload.is::<Rethrow>() {
+        if let Some(original) = PENDING.with(|pending| pending.borrow_mut().take()) {
+            return original;
+        }
+    }
+    payload
+}
+
+/// The catch boundary around the arms of a block without a `finally` block.
+pub fn rethrowing<T>(outcome: Result<T, Payload>) -> T {
+    outcome.unwrap_or_else(|payload| panic::resume_unwind(resolve(payload)))
+}
@@ -0,0 +1,208 @@
+//! The `Exception` trait and the machinery which lets a catch arm find the
+//! `Exception` implementation of a payload at runtime.
+
+use std::any::{self, Any, TypeId};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::RwLock;
+
+use crate::exception_ptr::ExceptionPtr;
+use crate::thrown::Thrown;
+
+/// A structured error which can be thrown and caught as `&dyn Exception`.
+///
+/// Types declared with `exception!` implement this trait. A catch arm of the
+/// form `Exception, e => expr` matches any payload whose type implements it,
+/// provided the value was thrown with `throw!` or its type was registered
+/// with [`register_exception`].
+pub trait Exception: fmt::Debug + fmt::Display + Send + 'static {
+    /// Returns the name of the exception type.
+    fn name(&self) -> &'static str {
+        any::type_name::<Self>()
+    }
+
+    /// Returns a human-readable description of the exception.
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns a numeric error code, if the exception has one.
+    fn code(&self) -> Option<i32> {
+        None
+    }
+
+    /// Returns the exception which caused this one, if any.
+    fn source(&self) -> Option<&dyn Exception> {
+        None
+    }
+}
+
+type LocalView = fn(&(dyn Any + Send)) -> Option<&dyn Exception>;
+type SharedView = fn(&(dyn Any + Send)) -> Option<&(dyn Exception + Sync)>;
+
+/// Views a payload of a known type as an exception.
+#[derive(Clone, Copy)]
+pub struct Cast {
+    local: LocalView,
+    /// Set if the type is `Sync`, so the exception may be borrowed on
+    /// several threads at once.
+    shared: Option<SharedView>,
+}
+
+impl Cast {
+    fn local<T: Exception>() -> Self {
+        Cast {
+            local: |value| value.downcast_ref::<T>().map(|e| e as &dyn Exception),
+            shared: None,
+        }
+    }
+
+    fn shared<T: Exception + Sync>() -> Self {
+        Cast {
+            local: |value| value.downcast_ref::<T>().map(|e| e as &dyn Exception),
+            shared: Some(|value| {
+                value
+                    .downcast_ref::<T>()
+                    .map(|e| e as &(dyn Exception + Sync))
+            }),
+        }
+    }
+
+    pub(crate) fn view(self, value: &(dyn Any + Send)) -> Option<&dyn Exception> {
+        (self.local)(value)
+    }
+
+    pub(crate) fn view_shared(self, value: &(dyn Any + Send)) -> Option<&(dyn Exception + Sync)> {
+        self.shared?(value)
+    }
+}
+
+static REGISTRY: RwLock<Vec<(TypeId, Cast)>> = RwLock::new(Vec::new());
+
+/// Lets `Exception` arms catch payloads of type `T` which were raised with
+/// `panic_any` rather than `throw!`.
+/// Declaring `T` with `exception!` doesn't register it.
+pub fn register_exception<T: Exception>() {
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    if registry.iter().all(|&(id, _)| id != TypeId::of::<T>()) {
+        registry.push((TypeId::of::<T>(), Cast::local::<T>()));
+    }
+}
+
+/// Returns the payload as an exception, looking inside a [`Thrown`]
+/// envelope, inside an [`ExceptionPtr`] and then in the registry.
+pub fn downcast_exception(payload: &(dyn Any + Send)) -> Option<&dyn Exception> {
+    if let Some(ptr) = payload.downcast_ref::<ExceptionPtr>() {
+        return ptr.exception().map(|e| e as &dyn Exception);
+    }
+    let (cast, value) = find_cast(payload)?;
+    cast.view(value)
+}
+
+/// Like [`downcast_exception`], but only finds exceptions whose type is
+/// `Sync`. Used by [`ExceptionPtr`], whose payload may be shared.
+pub(crate) fn downcast_shared_exception(
+    payload: &(dyn Any + Send),
+) -> Option<&(dyn Exception + Sync)> {
+    let (cast, value) = find_cast(payload)?;
+    cast.view_shared(value)
+}
+
+/// Finds the [`Cast`] for the payload, or for the value inside its
+/// [`Thrown`] envelope, along with the value it applies to.
+fn find_cast(payload: &(dyn Any + Send)) -> Option<(Cast, &(dyn Any + Send))> {
+    if let Some(thrown) = payload.downcast_ref::<Thrown>() {
+        return Some((thrown.cast()?, thrown.value()));
+    }
+    let id = payload.type_id();
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .iter()
+        .find(|&&(other, _)| other == id)
+        .map(|&(_, cast)| (cast, payload))
+}
+
+/// Picks the [`Cast`] for a type at compile time if it implements
+/// [`Exception`]. `throw!` calls `(&&&CastFor::new(&value)).cast()`; method
+/// resolution finds the `SyncExceptionCast` impl if the value is a `Sync`
+/// exception, the `ExceptionCast` impl if it is any other exception, and
+/// falls back to `PlainCast` otherwise.
+pub struct CastFor<T>(PhantomData<T>);
+
+impl<T> CastFor<T> {
+    pub fn new(_: &T) -> Self {
+        CastFor(PhantomData)
+    }
+}
+
+pub trait SyncExceptionCast {
+    fn cast(&self) -> Option<Cast>;
+}
+
+impl<T: Exception + Sync> SyncExceptionCast for &&CastFor<T> {
+    fn cast(&self) -> Option<Cast> {
+        Some(Cast::shared::<T>())
+    }
+}
+
+pub trait ExceptionCast {
+    fn cast(&self) -> Option<Cast>;
+}
+
+impl<T: Exception> ExceptionCast for &CastFor<T> {
+    fn cast(&self) -> Option<Cast> {
+        Some(Cast::local::<T>())
+    }
+}
+
+pub trait PlainCast {
+    fn cast(&self) -> Option<Cast>;
+}
+
+impl<T> PlainCast for CastFor<T> {
+    fn cast(&self) -> Option<Cast> {
+        None
+    }
+}
+
+/// Implemented by `exception_family!` for `dyn Family`. Finds the family
+/// member a payload holds, probing for each member the way a `T` arm does.
+pub trait Family {
+    fn cast(value: &(dyn Any + Send)) -> Option<&Self>;
+}
+
+/// Returns the payload as a member of the family `F`, looking inside a
+/// [`Thrown`] envelope, and inside an [`ExceptionPtr`] for members which
+/// are `Sync`.
+pub fn downcast_family<F: Family + ?Sized>(payload: &(dyn Any + Send)) -> Option<&F> {
+    F::cast(payload)
+}
+
+/// Implemented by `exception_family!` for each member `T` of the family `F`
+/// as `Member<dyn F>`.
+pub trait Member<F: ?Sized> {}
+
+/// Checks the arms that follow an unguarded family arm. `rsexcept!` calls
+/// `(&&HiddenBy::<T, dyn F>::default()).check()` for each later arm type `T`,
+/// which resolves to the deprecated `Hidden::check` if `T` is a member of
+/// `F`.
+pub struct HiddenBy<T: ?Sized, F: ?Sized>(PhantomData<T>, PhantomData<F>);
+
+impl<T: ?Sized, F: ?Sized> Default for HiddenBy<T, F> {
+    fn default() -> Self {
+        HiddenBy(PhantomData, PhantomData)
+    }
+}
+
+pub trait Hidden {
+    #[deprecated(note = "this catch arm is unreachable: an earlier family arm catches its type")]
+    fn check(&self) {}
+}
+
+impl<T: Member<F> + ?Sized, F: ?Sized> Hidden for &HiddenBy<T, F> {}
+
+pub trait Reachable {
+    fn check(&self) {}
+}
+
+impl<T: ?Sized, F: ?Sized> Reachable for HiddenBy<T, F> {}
@@ -0,0 +1,367 @@
+//! A shared handle to a caught panic, for raising it again elsewhere.
+
+use std::any::{self, Any};
+use std::fmt;
+use std::marker::PhantomData;
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+use crate::exception::{downcast_exception, downcast_shared_exception, Exception};
+use crate::group::ExceptionGroup;
+use crate::payload::downcast_ref;
+use crate::thrown::Thrown;
+
+type Payload = Box<dyn Any + Send>;
+
+/// A clonable handle to the payload of a caught panic, like C++'s
+/// `std::exception_ptr`. It can be stored, sent to other threads and
+/// raised again any number of times with [`rethrow`](ExceptionPtr::rethrow).
+///
+/// [`rethrow`](ExceptionPtr::rethrow) called on the last handle raises the
+/// original payload, which every arm of `rsexcept!` sees as usual. While
+/// other handles exist, it raises the `ExceptionPtr` instead, and the
+/// payload may be borrowed on several threads at once, so it is only handed
+/// out as a `T` which is `Sync`. A `T` arm for such a type, a `message` arm,
+/// and `Exception` and `dyn Family` arms for exceptions whose type is
+/// `Sync`, see through the rethrown `ExceptionPtr` the same way they see
+/// through a [`Thrown`] envelope. Arms for types which aren't `Sync`,
+/// thrown arms, `move` and `mut` arms, which would take or change the
+/// shared payload, and `catch*` blocks don't; the catch-all arm binds the
+/// `ExceptionPtr` itself.
+/// # Examples
+/// ```
+/// use rsexcept::{rsexcept, throw, ExceptionPtr};
+/// let ptr = std::thread::spawn(|| rsexcept! {
+///     try {
+///         throw!(42u32)
+///     }
+///     catch {
+///         _, e => ExceptionPtr::new(e)
+///     }
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(ptr.type_name(), "u32");
+/// let n = rsexcept! {
+///     try {
+///         ptr.rethrow()
+///     }
+///     catch {
+///         u32, n => *n
+///     }
+/// };
+/// assert_eq!(n, 42);
+/// ```
+#[derive(Clone)]
+pub struct ExceptionPtr {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Never replaced or changed. The mutex only makes the payload `Sync`;
+    /// it is locked just long enough to find a `T` in it.
+    payload: Mutex<Payload>,
+    type_name: &'static str,
+}
+
+impl ExceptionPtr {
+    /// Takes ownership of a caught payload. A payload which already is an
+    /// `ExceptionPtr` is returned as it is.
+    pub fn new(payload: Payload) -> Self {
+        match payload.downcast::<ExceptionPtr>() {
+            Ok(ptr) => *ptr,
+            Err(payload) => ExceptionPtr::with_name(type_name(&*payload), payload),
+        }
+    }
+
+    /// Creates a handle to `value` without raising it first, like C++'s
+    /// `std::make_exception_ptr`.
+    pub fn from_value<T: Any + Send>(value: T) -> Self {
+        ExceptionPtr::with_name(any::type_name::<T>(), Box::new(value))
+    }
+
+    fn with_name(type_name: &'static str, payload: Payload) -> Self {
+        ExceptionPtr {
+            inner: Arc::new(Inner {
+                payload: Mutex::new(payload),
+                type_name,
+            }),
+        }
+    }
+
+    /// Raises the payload again on the current thread. Panics raised this
+    /// way don't run the panic hook, as with `resume_unwind`.
+    ///
+    /// If this is the last handle to the payload, the original payload is
+    /// raised, so every catch arm sees it as if it had never been caught.
+    /// Otherwise the raised payload is the `ExceptionPtr` itself; clone the
+    /// handle first to keep it.
+    pub fn rethrow(self) -> ! {
+        match Arc::try_unwrap(self.inner) {
+            Ok(inner) => panic::resume_unwind(
+                inner
+                    .payload
+                    .into_inner()
+                    .unwrap_or_else(|e| e.into_inner()),
+            ),
+            Err(inner) => panic::resume_unwind(Box::new(ExceptionPtr { inner })),
+        }
+    }
+
+    /// Returns the payload, or the value inside its [`Thrown`] envelope, if
+    /// it is a `T`. The payload may be shared with other threads, so `T`
+    /// must be `Sync`.
+    pub fn downcast_ref<T: Any + Sync>(&self) -> Option<&T> {
+        let payload = self.inner.payload.lock().unwrap_or_else(|e| e.into_inner());
+        let value: *const T = downcast_ref::<T>(&**payload)?;
+        // SAFETY: the payload lives as long as `self` and is never changed,
+        // and `T` is `Sync`, so it may be borrowed on any number of threads.
+        Some(unsafe { &*value })
+    }
+
+    /// Returns the payload as an [`Exception`], if its type implements the
+    /// trait and is `Sync`, and it was thrown with `throw!` or its type was
+    /// registered with [`register_exception`](crate::register_exception).
+    pub fn exception(&self) -> Option<&(dyn Exception + Sync)> {
+        let payload = self.inner.payload.lock().unwrap_or_else(|e| e.into_inner());
+        let exception: *const (dyn Exception + Sync) = downcast_shared_exception(&**payload)?;
+        // SAFETY: as in `downcast_ref`.
+        Some(unsafe { &*exception })
+    }
+
+    /// Returns the name of the payload's type. For values thrown with
+    /// `throw!` and for exceptions this is the type of the value; for
+    /// other payloads whose type is unknown it is `Box<dyn Any>`.
+    pub fn type_name(&self) -> &'static str {
+        self.inner.type_name
+    }
+
+    /// Returns `true` if both handles refer to the same payload.
+    pub fn ptr_eq(&self, other: &ExceptionPtr) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl From<Payload> for ExceptionPtr {
+    fn from(payload: Payload) -> Self {
+        ExceptionPtr::new(payload)
+    }
+}
+
+impl fmt::Debug for ExceptionPtr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExceptionPtr")
+            .field(&self.type_name())
+            .finish()
+    }
+}
+
+/// Picks at compile time how a catch arm for `T` looks at a payload.
+/// `rsexcept!` calls `(&&Probe::<T>::default()).get(payload)`; method
+/// resolution finds the `SyncProbe` impl, which also looks inside an
+/// [`ExceptionPtr`], only if `T` is `Sync`, and falls back to `LocalProbe`
+/// otherwise.
+pub struct Probe<T>(PhantomData<T>);
+
+impl<T> Default for Probe<T> {
+    fn default() -> Self {
+        Probe(PhantomData)
+    }
+}
+
+pub trait SyncProbe<T> {
+    fn get<'a>(&self, payload: &'a (dyn Any + Send)) -> Option<&'a T>;
+}
+
+impl<T: Any + Sync> SyncProbe<T> for &Probe<T> {
+    fn get<'a>(&self, payload: &'a (dyn Any + Send)) -> Option<&'a T> {
+        match payload.downcast_ref::<ExceptionPtr>() {
+            Some(ptr) => ptr.downcast_ref(),
+            None => downcast_ref(payload),
+        }
+    }
+}
+
+pub trait LocalProbe<T> {
+    fn get<'a>(&self, payload: &'a (dyn Any + Send)) -> Option<&'a T>;
+}
+
+impl<T: Any> LocalProbe<T> for Probe<T> {
+    fn get<'a>(&self, payload: &'a (dyn Any + Send)) -> Option<&'a T> {
+        downcast_ref(payload)
+    }
+}
+
+fn type_name(payload: &(dyn Any + Send)) -> &'static str {
+    if let Some(thrown) = payload.downcast_ref::<Thrown>() {
+        thrown.type_name()
+    } else if let Some(e) = downcast_exception(payload) {
+        e.name()
+    } else if payload.is::<&'static str>() {
+        any::type_name::<&str>()
+    } else if payload.is::<String>() {
+        any::type_name::<String>()
+    } else if payload.is::<ExceptionGroup>() {
+        any::type_name::<ExceptionGroup>()
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExceptionPtr;
+    use std::panic::{self, panic_any};
+    use std::thread;
+
+    fn caught<F: FnOnce() + panic::UnwindSafe>(f: F) -> ExceptionPtr {
+        ExceptionPtr::new(crate::run(f).fold(|()| panic!("expected a panic"), |c| c.into_payload()))
+    }
+
+    #[test]
+    fn rethrow_on_other_threads() {
+        let ptr = caught(|| crate::throw!(String::from("lost")));
+        assert_eq!(ptr.type_name(), "alloc::string::String");
+        assert_eq!(ptr.downcast_ref::<String>().unwrap(), "lost");
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let ptr = ptr.clone();
+                thread::spawn(move || {
+                    crate::rsexcept! {
+                        try {
+                            ptr.rethrow()
+                        }
+                        catch {
+                            u8, _ => String::new(),
+                            String, s, _ => format!("thrown {}", s),
+                            String, s => s.clone()
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), "lost");
+        }
+    }
+    #[test]
+    fn catch_all_and_move_arms() {
+        let ptr = caught(|| panic_any(5u8));
+        let again = crate::rsexcept! {
+            try {
+                ptr.clone().rethrow()
+            }
+            catch {
+                move u8, _ => None,
+                _, e => Some(ExceptionPtr::new(e))
+            }
+        };
+        assert!(again.unwrap().ptr_eq(&ptr));
+        assert_eq!(format!("{:?}", ptr), r#"ExceptionPtr("Box<dyn Any>")"#);
+        let ptr = ExceptionPtr::from_value(7u8);
+        assert_eq!(format!("{:?}", ptr), r#"ExceptionPtr("u8")"#);
+        assert_eq!(
+            crate::rsexcept!(try { ptr.rethrow() } catch { u8, n => *n }),
+            7
+        );
+    }
+    #[test]
+    fn last_handle_raises_original_payload() {
+        let ptr = caught(|| crate::throw!(String::from("mine")));
+        let again = ptr.clone();
+        let res = crate::rsexcept! {
+            try {
+                ptr.rethrow()
+            }
+            catch {
+                move String, _ => String::new(),
+                _, e => ExceptionPtr::new(e).type_name().to_string()
+            }
+        };
+        assert_eq!(res, "alloc::string::String");
+        let res = crate::rsexcept! {
+            try {
+                again.rethrow()
+            }
+            catch {
+                move String, s => s + "!",
+            }
+        };
+        assert_eq!(res, "mine!");
+    }
+    #[test]
+    fn exception_and_family_arms() {
+        crate::exception! {
+            struct Refused { port: u16 } : "connection to port {port} refused";
+        }
+        crate::exception_family! {
+            NetError > { Refused }
+        }
+        let ptr = caught(|| crate::throw!(Refused { port: 80 }));
+        assert_eq!(ptr.exception().unwrap().name(), "Refused");
+        let res = crate::rsexcept! {
+            try {
+                ptr.clone().rethrow()
+            }
+            catch {
+                Exception, e => e.message()
+            }
+        };
+        assert_eq!(res, "connection to port 80 refused");
+        let res = crate::rsexcept! {
+            try {
+                ptr.clone().rethrow()
+            }
+            catch {
+                dyn NetError, e => e.name()
+            }
+        };
+        assert_eq!(res, "Refused");
+    }
+    #[test]
+    fn nested_rethrow_of_held_payload() {
+        let ptr = caught(|| panic!("boom"));
+        let res = crate::rsexcept! {
+            try {
+                ptr.clone().rethrow()
+            }
+            catch {
+                message, outer => crate::rsexcept! {
+                    try {
+                        ptr.clone().rethrow()
+                    }
+                    catch {
+                        message, inner => (outer.to_string(), inner.to_string())
+                    }
+                }
+            }
+        };
+        assert_eq!(res, (String::from("boom"), String::from("boom")));
+    }
+    #[test]
+    fn nested_rethrow_on_other_thread() {
+        let ptr = caught(|| panic!("{}", "boom"));
+        let res = crate::rsexcept! {
+            try {
+                ptr.clone().rethrow()
+            }
+            catch {
+                String, outer => {
+                    let ptr = ptr.clone();
+                    let inner = thread::spawn(move || {
+                        crate::rsexcept! {
+                            try {
+                                ptr.rethrow()
+                            }
+                            catch {
+                                String, inner => inner.clone()
+                            }
+                        }
+                    });
+                    (outer.clone(), inner.join().unwrap())
+                }
+            }
+        };
+        assert_eq!(res, (String::from("boom"), String::from("boom")));
+    }
+}
@@ -0,0 +1,88 @@
+//! Support code for `finally` blocks.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::current::resolve;
+use crate::thrown::Thrown;
+
+thread_local! {
+    /// Panics raised by `finally` blocks while a panic which wasn't thrown
+    /// with `throw!` was propagating. Cleared when a try block starts
+    /// outside of any other, so it only holds panics from the latest one.
+    static SUPPRESSED: RefCell<Vec<Box<dyn Any + Send>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Takes the payloads of every panic which was suppressed on the current
+/// thread because it was raised by a `finally` block while another panic
+/// was already propagating. The oldest payload comes first.
+///
+/// Only panics suppressed since the current thread last entered a try block
+/// which isn't nested in another one are kept, so call this right after
+/// catching the panic they were suppressed by.
+///
+/// Panics suppressed while a value thrown with `throw!` was propagating
+/// are attached to its envelope instead; see [`suppressed`].
+pub fn take_suppressed() -> Vec<Box<dyn Any + Send>> {
+    SUPPRESSED.with(|suppressed| suppressed.take())
+}
+
+/// Forgets the panics suppressed by earlier blocks.
+pub(crate) fn clear_suppressed() {
+    let _ = SUPPRESSED.try_with(|suppressed| suppressed.borrow_mut().clear());
+}
+
+/// Returns the payloads of the panics which `finally` blocks raised while
+/// `payload` was propagating, oldest first. This is always empty unless
+/// `payload` was thrown with `throw!`.
+/// # Examples
+/// ```
+/// use rsexcept::{panic_message, rsexcept, suppressed, throw};
+/// let closed = rsexcept! {
+///     try {
+///         rsexcept! {
+///             try {
+///                 throw!("write failed")
+///             }
+///             catch {}
+///             finally {
+///                 panic!("close failed");
+///             }
+///         }
+///     }
+///     catch {
+///         _, e => panic_message(&*suppressed(&*e)[0]).unwrap().to_string()
+///     }
+/// };
+/// assert_eq!(closed, "close failed");
+/// ```
+pub fn suppressed(payload: &(dyn Any + Send)) -> &[Box<dyn Any + Send>] {
+    match payload.downcast_ref::<Thrown>() {
+        Some(thrown) => thrown.suppressed(),
+        None => &[],
+    }
+}
+
+/// Runs `cleanup` after the try/catch part of a `rsexcept!` block finished
+/// with `outcome`, then either returns the value or continues unwinding.
+///
+/// If both the outcome and the cleanup panicked, the original panic keeps
+/// propagating. The cleanup's panic is attached to the original one if it
+/// was thrown with `throw!`, and recorded for [`take_suppressed`]
+/// otherwise.
+pub fn finally<T, F: FnOnce()>(outcome: Result<T, Box<dyn Any + Send>>, cleanup: F) -> T {
+    let outcome = outcome.map_err(resolve);
+    let cleanup = panic::catch_unwind(AssertUnwindSafe(cleanup));
+    match (outcome, cleanup) {
+        (Ok(v), Ok(())) => v,
+        (Ok(_), Err(e)) | (Err(e), Ok(())) => panic::resume_unwind(e),
+        (Err(mut e), Err(secondary)) => {
+            match e.downcast_mut::<Thrown>() {
+                Some(thrown) => thrown.suppress(secondary),
+                None => SUPPRESSED.with(|suppressed| suppressed.borrow_mut().push(secondary)),
+            }
+            panic::resume_unwind(e)
+        }
+    }
+}
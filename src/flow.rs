@@ -0,0 +1,58 @@
+//! Support code for carrying `return`, `break`, `continue` and `?` out of
+//! the closure that `rsexcept!` runs the try block in.
+
+use std::any::Any;
+use std::panic;
+
+/// Why a try block exited early.
+pub enum Exit<R> {
+    /// The try block executed `return` (or `?` on a failure) with this value.
+    Return(R),
+    /// The try block executed the `break` or `continue` with this index.
+    Jump(usize),
+}
+
+/// Implemented for the types which support the `?` operator. The `?`
+/// operators in a try block are rewritten to `.__rsexcept_question()?`, so
+/// that a failure returns from the enclosing function instead of the
+/// closure.
+pub trait Question<R> {
+    type Output;
+
+    fn __rsexcept_question(self) -> Result<Self::Output, Exit<R>>;
+}
+
+impl<T, E, U, F: From<E>> Question<Result<U, F>> for Result<T, E> {
+    type Output = T;
+
+    fn __rsexcept_question(self) -> Result<T, Exit<Result<U, F>>> {
+        self.map_err(|e| Exit::Return(Err(From::from(e))))
+    }
+}
+
+impl<T, U> Question<Option<U>> for Option<T> {
+    type Output = T;
+
+    fn __rsexcept_question(self) -> Result<T, Exit<Option<U>>> {
+        self.ok_or(Exit::Return(None))
+    }
+}
+
+/// Implemented for the types which support the `?` operator inside a
+/// `throwing try` block, where `?` is rewritten to `.__rsexcept_throw()`.
+pub trait Throw {
+    type Output;
+
+    fn __rsexcept_throw(self) -> Self::Output;
+}
+
+impl<T, E: Any + Send> Throw for Result<T, E> {
+    type Output = T;
+
+    fn __rsexcept_throw(self) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => panic::panic_any(e),
+        }
+    }
+}
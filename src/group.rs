@@ -0,0 +1,145 @@
+//! Exception groups, and the support code for `catch*` blocks.
+
+use std::any::Any;
+use std::fmt;
+use std::panic;
+use std::thread;
+
+use crate::chain::Link;
+use crate::payload::{downcast, Shell};
+
+type Payload = Box<dyn Any + Send>;
+
+/// A payload made of several payloads, such as the panics of a batch of
+/// worker threads.
+///
+/// Throw a group like any other value. A `catch* { ... }` block handles its
+/// members by type and re-raises a smaller group with the members no arm
+/// handled.
+pub struct ExceptionGroup {
+    members: Vec<Payload>,
+}
+
+impl ExceptionGroup {
+    /// Creates a group with the given members.
+    pub fn new(members: Vec<Payload>) -> Self {
+        ExceptionGroup { members }
+    }
+
+    /// Collects the values of `results`, or every panic among them into a
+    /// group.
+    /// # Examples
+    /// ```
+    /// use rsexcept::ExceptionGroup;
+    /// let workers: Vec<_> = (0..4)
+    ///     .map(|i| std::thread::spawn(move || if i % 2 == 0 { i } else { panic!("worker {}", i) }))
+    ///     .collect();
+    /// let group = ExceptionGroup::collect(workers.into_iter().map(|w| w.join())).unwrap_err();
+    /// assert_eq!(group.len(), 2);
+    /// ```
+    pub fn collect<T, I: IntoIterator<Item = thread::Result<T>>>(
+        results: I,
+    ) -> Result<Vec<T>, ExceptionGroup> {
+        let mut values = Vec::new();
+        let mut members = Vec::new();
+        for result in results {
+            match result {
+                Ok(v) => values.push(v),
+                Err(payload) => members.push(payload),
+            }
+        }
+        if members.is_empty() {
+            Ok(values)
+        } else {
+            Err(ExceptionGroup::new(members))
+        }
+    }
+
+    /// Returns the members.
+    pub fn members(&self) -> &[Payload] {
+        &self.members
+    }
+
+    /// Takes the members out.
+    pub fn into_members(self) -> Vec<Payload> {
+        self.members
+    }
+
+    /// Returns the number of members.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the group has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+impl fmt::Debug for ExceptionGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.members.iter().map(|m| Link::new(&**m).to_string()))
+            .finish()
+    }
+}
+
+/// The payload of a `catch*` block, split up among its arms.
+pub struct Split {
+    members: Vec<Payload>,
+    /// The envelope the group was found in, or `None` if the payload
+    /// wasn't a group.
+    shell: Option<Shell>,
+}
+
+impl Split {
+    /// Splits a group into its members. Any other payload is treated as a
+    /// group of one.
+    pub fn new(payload: Payload) -> Self {
+        match downcast::<ExceptionGroup>(payload) {
+            Ok((group, shell)) => Split {
+                members: group.members,
+                shell: Some(shell),
+            },
+            Err(payload) => Split {
+                members: vec![payload],
+                shell: None,
+            },
+        }
+    }
+
+    /// Removes and returns the members which are a `T`.
+    pub fn take<T: Any + Send>(&mut self) -> Vec<T> {
+        let mut taken = Vec::new();
+        let mut rest = Vec::new();
+        for member in self.members.drain(..) {
+            match downcast::<T>(member) {
+                Ok((v, _)) => taken.push(v),
+                Err(member) => rest.push(member),
+            }
+        }
+        self.members = rest;
+        taken
+    }
+
+    /// Removes and returns every remaining member.
+    pub fn take_all(&mut self) -> Vec<Payload> {
+        std::mem::take(&mut self.members)
+    }
+
+    /// Returns the value of the last arm which ran if every member was
+    /// handled. Otherwise re-raises the members which are left, as a
+    /// group if the payload was a group.
+    pub fn finish<R>(self, handled: Option<R>) -> R {
+        match (handled, self.shell) {
+            (Some(v), _) if self.members.is_empty() => v,
+            (_, Some(shell)) => {
+                panic::resume_unwind(shell.rebox(ExceptionGroup::new(self.members)))
+            }
+            (_, None) => match self.members.into_iter().next() {
+                Some(payload) => panic::resume_unwind(payload),
+                None => unreachable!("an arm handled the payload"),
+            },
+        }
+    }
+}
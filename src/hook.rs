@@ -0,0 +1,84 @@
+//! The process-wide panic hook used by `rsexcept!`.
+//!
+//! The hook is installed once and wraps whichever hook was registered
+//! before it. Panics raised on a thread that is currently executing a
+//! `rsexcept!` try block are silenced. Every other panic is forwarded to
+//! the original hook, and uncaught values thrown with `throw!` are then
+//! reported with their chain of causes, and uncaught exception groups with
+//! their members.
+
+use std::cell::Cell;
+use std::panic;
+use std::sync::Once;
+
+use crate::chain::report;
+use crate::finally::clear_suppressed;
+use crate::group::ExceptionGroup;
+use crate::thrown::Thrown;
+
+thread_local! {
+    /// Number of `rsexcept!` try blocks the current thread is inside of.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+static INSTALL: Once = Once::new();
+
+/// Installs the silencing panic hook if it isn't installed yet.
+///
+/// `rsexcept!` and `throw!` call this automatically, so you only need it if
+/// you want the hook in place before the first try block runs. For panics with a
+/// value thrown by `throw!` or an [`ExceptionGroup`], the original hook's
+/// output is followed by the value, its causes and the group's members. Any
+/// hook registered with [`std::panic::set_hook`] *after* this call replaces
+/// the silencing hook.
+pub fn install_hook() {
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if is_silenced() {
+                return;
+            }
+            previous(info);
+            let payload = info.payload();
+            if payload.is::<Thrown>() || payload.is::<ExceptionGroup>() {
+                let mut out = Vec::new();
+                let _ = report(&mut out, payload);
+                eprint!("{}", String::from_utf8_lossy(&out));
+            }
+        }));
+    });
+}
+
+/// Returns `true` if panics raised on the current thread are being silenced,
+/// i.e. the thread is currently executing a `rsexcept!` try block.
+pub fn is_silenced() -> bool {
+    // The hook may run while thread-locals are being torn down.
+    DEPTH.try_with(|depth| depth.get() > 0).unwrap_or(false)
+}
+
+/// Silences panics on the current thread until the returned guard is
+/// dropped. Entering the outermost try block also forgets the panics
+/// suppressed by earlier blocks.
+pub fn silence() -> Silence {
+    install_hook();
+    let previous = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    if previous == 0 {
+        clear_suppressed();
+    }
+    Silence { previous }
+}
+
+/// Guard returned by [`silence`]. Dropping it restores the silencing state
+/// that was active when it was created, so nested guards unwind in stack
+/// order no matter how their try blocks were exited.
+#[must_use]
+pub struct Silence {
+    previous: usize,
+}
+
+impl Drop for Silence {
+    fn drop(&mut self) {
+        let previous = self.previous;
+        let _ = DEPTH.try_with(|depth| depth.set(previous));
+    }
+}
@@ -1,3 +1,53 @@
+mod abort;
+mod builder;
+mod chain;
+mod current;
+mod exception;
+mod exception_ptr;
+mod finally;
+mod flow;
+mod group;
+mod hook;
+mod outcome;
+mod payload;
+mod retry;
+mod thrown;
+mod unwind;
+
+pub use builder::{Catch, Try};
+pub use chain::{chain, Chain, Link};
+pub use current::current_exception;
+pub use exception::{register_exception, Exception};
+pub use exception_ptr::ExceptionPtr;
+pub use finally::{suppressed, take_suppressed};
+pub use group::ExceptionGroup;
+pub use hook::{install_hook, is_silenced};
+pub use outcome::{run, Caught, Outcome};
+pub use payload::panic_message;
+pub use retry::attempt;
+pub use thrown::Thrown;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::abort::abort;
+    pub use crate::current::{rethrow, rethrowing, Handling};
+    pub use crate::exception::{
+        downcast_exception, downcast_family, CastFor, ExceptionCast, Family, Hidden, HiddenBy,
+        Member, PlainCast, Reachable, SyncExceptionCast,
+    };
+    pub use crate::exception_ptr::{LocalProbe, Probe, SyncProbe};
+    pub use crate::finally::finally;
+    pub use crate::flow::{Exit, Question, Throw};
+    pub use crate::group::Split;
+    pub use crate::hook::silence;
+    pub use crate::payload::{downcast, downcast_mut, downcast_thrown};
+    pub use crate::retry::enter_attempt;
+    pub use crate::thrown::throw;
+    pub use crate::unwind::{
+        assert_unwind_safe, try_block_must_be_unwind_safe, TryBlockUnwindSafe,
+    };
+}
+
 /// Executes the code in the try block. If it panics, attempts to match the
 /// type and pattern of the panic to an arm in the catch block. Each arm takes
 /// the form `type, pattern => expr`. If a catch arm panics, that panic
@@ -5,8 +55,149 @@
 /// payload, then the originally thrown panic propagates up the callstack
 /// as if there was no `try`/`catch` block. The types of the try block
 /// and each catch arm must agree.
+///
+/// Any arm may carry a guard, as in `T, pattern if condition => expr`. If
+/// the guard evaluates to `false`, matching continues with the next arm.
+///
+/// An arm may list several types separated by `|`, as in
+/// `i32 | i64, n => n.to_string()`. The handler is instantiated once for each
+/// type. Adding `as dyn Trait` after the types, as in
+/// `i32 | String as dyn Display, d => ...`, binds all of them as a
+/// `&dyn Trait` instead.
+///
+/// A `message, pattern => expr` arm matches the payload of any `panic!`,
+/// whether it was a `&'static str` (`panic!("literal")`) or a `String`
+/// (`panic!("{}", x)`), and binds it as a `&str`.
+///
+/// Values thrown with [`throw!`] are wrapped in a [`Thrown`] envelope, but
+/// every arm except the catch-all looks inside it, so a `T` arm matches a
+/// thrown `T`. An arm of the form `T, pattern, thrown => expr` only matches
+/// a `T` thrown with `throw!`, and binds the envelope as a `&Thrown` to the
+/// second pattern. The catch-all arm binds the payload as it was raised.
+///
+/// Arms borrow the payload. Prefixing an arm with `move`, as in
+/// `move T, pattern => expr`, takes the payload out of the panic by value
+/// instead, so the handler can own a `String` or a custom error. If the
+/// payload isn't a `T`, the original payload is passed on to the next arm.
+/// If it is a `T` but doesn't match the pattern, it is boxed again and
+/// passed on.
+///
+/// Prefixing an arm with `mut`, as in `mut T, pattern => expr`, binds the
+/// payload as a `&mut T`, so the handler can change it in place. If the
+/// handler then calls [`rethrow!`], as in
+/// `mut T, e => { e.context.push(id); rethrow!() }`, the changed payload is
+/// re-raised in its original box, inside its [`Thrown`] envelope if it had
+/// one. Payloads still shared through an [`ExceptionPtr`] can't be
+/// changed, so `mut` arms don't match them.
+///
+/// A `T, pattern` arm, a `message, pattern` arm or a `_, pattern` arm may
+/// use `retry` or `retry max n` as its handler. It runs the try block again
+/// from the start; [`attempt`] returns the number of the current attempt,
+/// starting at 1. Once `n` attempts have been made, the last panic
+/// propagates as if no arm had matched. A `_` arm which retries binds the
+/// payload as a `&(dyn Any + Send)`, since it stays in place for the next
+/// attempt. The other arm kinds can't retry, and using `retry` in them is
+/// a compile error.
+///
+/// An `Exception, pattern => expr` arm matches any payload whose type
+/// implements [`Exception`] and binds it as a `&dyn Exception`. This works
+/// for values thrown with [`throw!`], and for values raised with
+/// `panic_any` if their type was registered with [`register_exception`].
+/// Declaring a type with [`exception!`] doesn't register it, so until
+/// `register_exception::<T>()` has been called, a `T` raised with
+/// `panic_any` falls through to the later arms.
+///
+/// A `dyn Family, pattern => expr` arm matches any member of a family
+/// declared with [`exception_family!`] and binds it as a `&dyn Family`. If
+/// the arm has no guard, later arms for members of the family could never
+/// match, so they cause a deprecation warning.
+///
+/// The last arm may be a catch-all of the form `_, pattern => expr`. It
+/// matches any payload and binds the owned `Box<dyn Any + Send + 'static>`,
+/// so the payload can be stored, logged or sent to another thread. Arms
+/// after an unguarded catch-all could never match, so they are a compile
+/// error.
+///
+/// Writing `catch* { ... }` instead of `catch { ... }` splits an
+/// [`ExceptionGroup`] among the arms, like Python's `except*`. An arm of the
+/// form `T, pattern => expr` binds the members which are a `T` as a
+/// `Vec<T>`, and only runs if there is at least one. Every arm with members
+/// runs, in order, and the last one provides the value of the expression.
+/// A final `_, pattern => expr` arm takes the remaining members as a
+/// `Vec<Box<dyn Any + Send>>`. If any member is left unhandled, a group of
+/// the remaining members is re-raised. A payload which isn't a group is
+/// treated as a group of one, and re-raised unchanged if no arm takes it.
+/// Guards, `move`, `retry` and the other arm kinds aren't available in
+/// `catch*` blocks.
+///
+/// Inside an arm, [`current_exception`] gives access to the payload being
+/// handled, and [`rethrow!`] re-raises it unchanged. A payload raised
+/// again from the last handle of an [`ExceptionPtr`] is matched as usual.
+/// While the payload is still shared, arms for `Sync` types, `message`
+/// arms, and `Exception` and `dyn Family` arms for `Sync` exceptions see
+/// through it, on any thread.
+///
+/// An optional `finally` block may follow the catch block. It always runs
+/// last: after the try block completes, after a catch arm handles a panic,
+/// after a catch arm panics, and before an unmatched panic is re-raised. If
+/// the finally block panics while another panic is propagating, the
+/// original panic keeps propagating and the new one is recorded. If the
+/// original panic was thrown with [`throw!`], the new one is attached to
+/// its envelope and listed by [`suppressed`]; otherwise it can be
+/// retrieved with [`take_suppressed`].
+///
+/// An optional `else |value| { ... }` block may sit between the catch block
+/// and the finally block. It only runs if the try block didn't panic, and it
+/// receives the try block's value; its result becomes the result of the
+/// whole expression. Panics inside it are *not* caught by the catch arms.
+///
+/// The try block runs in a closure, so by default `return` and `?` in it
+/// leave that closure rather than the enclosing function, which usually
+/// fails to compile, and `break` and `continue` can't reach loops outside
+/// of it. Writing `flow try { ... }` instead of `try { ... }` makes the try
+/// block behave like an ordinary block as far as control flow is concerned:
+/// `return` and `?` leave the enclosing function, and `break` and
+/// `continue` act on the enclosing loop, or on the labeled loop they name,
+/// even from loops nested in the try block. The macro carries these exits
+/// out of the closure and replays them after the finally block has run.
+/// Closures, `async` blocks and `fn` items nested in the try block are left
+/// alone, so `return` and `?` inside them work as usual. `break` with a
+/// value isn't supported. The catch arms and the else block are treated the
+/// same way, except that `?` in them always returns from the enclosing
+/// function.
+///
+/// Writing `throwing try { ... }` instead of `flow try { ... }` makes `?` on
+/// an `Err(e)` inside the try block throw `e` as if by `panic_any(e)`, so a
+/// catch arm for the error type can handle it. Unhandled errors propagate
+/// like any other unmatched panic.
+///
+/// The try block runs in a closure which must be [`UnwindSafe`], so by
+/// default it can't capture a `&mut` reference or a `RefCell`. The compile
+/// error says that the try block captures a value which isn't unwind safe;
+/// it points at the whole `rsexcept!` invocation rather than at the capture.
+/// Writing `unsafe_unwind try { ... }` (or `unsafe_unwind flow try { ... }`
+/// and `unsafe_unwind throwing try { ... }`) wraps the closure in
+/// [`AssertUnwindSafe`] instead. Only do this if the captured state is
+/// never observed half-updated after a panic.
+///
+/// [`UnwindSafe`]: std::panic::UnwindSafe
+/// [`AssertUnwindSafe`]: std::panic::AssertUnwindSafe
 /// # Notes
 /// This *only* catches unwinding panics.
+///
+/// A plain `try` block is passed on as a single token tree, so its length
+/// doesn't matter. A `flow try` or `throwing try` block is rewritten by the
+/// macro, which recurses once for every few tokens and every longer group
+/// of tokens, so expanding a block of more than about fifty statements can hit
+/// the compiler's recursion limit (128 by default). Move part of the block
+/// into a function or raise the limit with `#![recursion_limit = "256"]` if
+/// it does. The same goes for the catch arms and the else block of such a
+/// block.
+///
+/// Panics raised inside the try block aren't printed. This is done by a
+/// process-wide panic hook (see [`install_hook`]) which only stays quiet for
+/// threads that are currently inside a try block, so other threads keep
+/// reporting their panics through the original hook.
 /// # Examples
 /// ```
 /// use rsexcept::rsexcept;
@@ -63,173 +254,5721 @@
 ///     assert_eq!("is_array", res);
 /// }
 /// ```
+/// ```
+/// use rsexcept::rsexcept;
+/// fn main() {
+///     let mut log = Vec::new();
+///     let res = rsexcept! {
+///         try {
+///             std::panic::panic_any(7)
+///         }
+///         catch {
+///             i32, n => n * 6
+///         }
+///         finally {
+///             log.push("cleaned up");
+///         }
+///     };
+///     assert_eq!(42, res);
+///     assert_eq!(log, ["cleaned up"]);
+/// }
+/// ```
+/// ```
+/// use rsexcept::rsexcept;
+/// fn main() {
+///     let payload = rsexcept! {
+///         try {
+///             std::panic::panic_any(3.5f64)
+///         }
+///         catch {
+///             i32, _ => None,
+///             _, e => Some(e)
+///         }
+///     };
+///     let payload = payload.unwrap();
+///     let n = std::thread::spawn(move || *payload.downcast_ref::<f64>().unwrap())
+///         .join()
+///         .unwrap();
+///     assert_eq!(3.5, n);
+/// }
+/// ```
+/// ```
+/// use rsexcept::rsexcept;
+/// fn parse_all(inputs: &[&str]) -> Result<Vec<i32>, std::num::ParseIntError> {
+///     let mut out = Vec::new();
+///     for input in inputs {
+///         let n = rsexcept! {
+///             flow try {
+///                 if input.is_empty() {
+///                     continue;
+///                 }
+///                 input.parse::<i32>()?
+///             }
+///             catch {
+///                 &str, _ => 0
+///             }
+///         };
+///         out.push(n);
+///     }
+///     Ok(out)
+/// }
+///
+/// fn main() {
+///     assert_eq!(parse_all(&["1", "", "3"]), Ok(vec![1, 3]));
+///     assert!(parse_all(&["1", "x"]).is_err());
+/// }
+/// ```
+/// ```compile_fail
+/// use rsexcept::rsexcept;
+/// fn main() {
+///     rsexcept! {
+///         try {
+///             std::panic::panic_any(3)
+///         }
+///         catch {
+///             _, _ => (),
+///             i32, _ => ()
+///         }
+///     }
+/// }
+/// ```
+/// ```compile_fail
+/// use rsexcept::rsexcept;
+/// fn main() {
+///     let mut count = 0;
+///     // `count` is captured by `&mut`, which isn't unwind safe.
+///     rsexcept! {
+///         try {
+///             count += 1;
+///         }
+///         catch {
+///             i32, _ => ()
+///         }
+///     }
+/// }
+/// ```
 #[macro_export]
 macro_rules! rsexcept {
-    (try $b:block catch { $( $t:ty, $p:pat => $handler:expr),* $(,)? }) => {
+    (try { $($body:tt)* } $($clauses:tt)*) => {
+        $crate::rsexcept!(@expand plain try_block_must_be_unwind_safe ($($clauses)*) [] { $($body)* })
+    };
+    (flow try { $($body:tt)* } $($clauses:tt)*) => {
+        $crate::rsexcept!(
+            @rw [] [] [] out yes (propagate (@expand flow try_block_must_be_unwind_safe ($($clauses)*)) []) $($body)*
+        )
+    };
+    (throwing try { $($body:tt)* } $($clauses:tt)*) => {
+        $crate::rsexcept!(
+            @rw [] [] [] out yes (throwing (@expand flow try_block_must_be_unwind_safe ($($clauses)*)) []) $($body)*
+        )
+    };
+    (unsafe_unwind try { $($body:tt)* } $($clauses:tt)*) => {
+        $crate::rsexcept!(@expand plain assert_unwind_safe ($($clauses)*) [] { $($body)* })
+    };
+    (unsafe_unwind flow try { $($body:tt)* } $($clauses:tt)*) => {
+        $crate::rsexcept!(@rw [] [] [] out yes (propagate (@expand flow assert_unwind_safe ($($clauses)*)) []) $($body)*)
+    };
+    (unsafe_unwind throwing try { $($body:tt)* } $($clauses:tt)*) => {
+        $crate::rsexcept!(@rw [] [] [] out yes (throwing (@expand flow assert_unwind_safe ($($clauses)*)) []) $($body)*)
+    };
+    // Looks for `return`, `break`, `continue` and `?` anywhere in the catch
+    // arms and the else block of a `flow try` or `throwing try` block. Only
+    // those which use them need to be rewritten by `@rw`. Groups are spliced
+    // into the tokens being searched. Every rule but the last two sees plain
+    // tokens before its match, so the search skips up to eight tokens at
+    // once to keep the recursion depth down.
+    (@find_flow ($question:ident ($($next:tt)*) $labels:tt) $jumps:tt $body:tt) => {
+        $crate::rsexcept!($($next)* $jumps $body)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt { $($body:tt)* } $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [] $jumps [] out yes $ctx $($body)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt ( $($group:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt [ $($group:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt { $($group:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($group)* $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($rest)*)
+    };
+    (@find_flow $ctx:tt $jumps:tt $body:tt $a:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@find_flow $ctx $jumps $body $($rest)*)
+    };
+    (
+        @expand plain $unwind:ident (
+            catch * { $($arms:tt)* }
+            $( else |$v:ident $(: $vt:ty)?| { $($else_block:tt)* } )?
+            $( finally $f:block )?
+        )
+        $jumps:tt $body:tt
+    ) => {
+        $crate::rsexcept!(
+            @handlers $unwind $body ($( finally $f )?) $jumps
+            { catch * { $($arms)* } $( else |$v $(: $vt)?| { $($else_block)* } )? }
+        )
+    };
+    (
+        @expand plain $unwind:ident (
+            catch { $($arms:tt)* }
+            $( else |$v:ident $(: $vt:ty)?| { $($else_block:tt)* } )?
+            $( finally $f:block )?
+        )
+        $jumps:tt $body:tt
+    ) => {
+        $crate::rsexcept!(
+            @handlers $unwind $body ($( finally $f )?) $jumps
+            { catch { $($arms)* } $( else |$v $(: $vt)?| { $($else_block)* } )? }
+        )
+    };
+    // Rewrites the catch arms and the else block the same way as the try
+    // block, since they run in a closure too. `?` in them always returns
+    // from the enclosing function.
+    (
+        @expand flow $unwind:ident (
+            catch * { $($arms:tt)* }
+            $( else |$v:ident $(: $vt:ty)?| { $($else_block:tt)* } )?
+            $( finally $f:block )?
+        )
+        $jumps:tt $body:tt
+    ) => {
+        $crate::rsexcept!(
+            @find_flow (propagate (@handlers $unwind $body ($( finally $f )?)) []) $jumps
+            { catch * { $($arms)* } $( else |$v $(: $vt)?| { $($else_block)* } )? }
+            $($arms)* $($($else_block)*)?
+        )
+    };
+    (
+        @expand flow $unwind:ident (
+            catch { $($arms:tt)* }
+            $( else |$v:ident $(: $vt:ty)?| { $($else_block:tt)* } )?
+            $( finally $f:block )?
+        )
+        $jumps:tt $body:tt
+    ) => {
+        $crate::rsexcept!(
+            @find_flow (propagate (@handlers $unwind $body ($( finally $f )?)) []) $jumps
+            { catch { $($arms)* } $( else |$v $(: $vt)?| { $($else_block)* } )? }
+            $($arms)* $($($else_block)*)?
+        )
+    };
+    (@handlers $unwind:ident $body:tt ($($finally:tt)*) $jumps:tt { catch * { $($arms:tt)* } $($rest:tt)* }) => {
+        $crate::rsexcept!(
+            @build once $unwind $jumps $body
+            catch {
+                _, payload => {
+                    let mut split = $crate::__private::Split::new(payload);
+                    let mut handled = ::core::option::Option::None;
+                    $crate::rsexcept!(@split split handled; $($arms)*);
+                    split.finish(handled)
+                }
+            }
+            $($rest)* $($finally)*
+        )
+    };
+    (@handlers $unwind:ident $body:tt ($($finally:tt)*) $jumps:tt { catch { $($arms:tt)* } $($rest:tt)* }) => {
+        $crate::rsexcept!(
+            @find_retry ($unwind $jumps $body catch { $($arms)* } $($rest)* $($finally)*) $($arms)*
+        )
+    };
+    // Looks for a `retry` arm. The rules below only see plain tokens before
+    // `=> retry`, so the search skips up to eight tokens at once to keep the
+    // recursion depth down for blocks with many arms.
+    (@find_retry ($($args:tt)*)) => {
+        $crate::rsexcept!(@build once $($args)*)
+    };
+    (@find_retry ($($args:tt)*) => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt $b:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt $b:tt $c:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt $b:tt $c:tt $d:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt $b:tt $c:tt $d:tt $e:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry ($($args:tt)*) $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt => retry $($rest:tt)*) => {
+        $crate::rsexcept!(@build retry $($args)*)
+    };
+    (@find_retry $args:tt $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@find_retry $args $($rest)*)
+    };
+    (@find_retry $args:tt $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@find_retry $args $($rest)*)
+    };
+    // The arms run in a closure of their own, so that the block can swap
+    // the marker `rethrow!()` unwinds with for the payload it stands for.
+    (
+        @build $mode:ident $unwind:ident $jumps:tt $body:tt
+        catch { $($arms:tt)* }
+        $( else |$v:ident $(: $vt:ty)?| $else_block:block )?
+        finally $f:block
+    ) => {
+        match $crate::__private::finally(
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                #[allow(unused_imports)]
+                use $crate::__private::Question as _;
+                $crate::rsexcept! {
+                    @flow $mode $unwind $body
+                    catch { $($arms)* }
+                    $( else |$v $(: $vt)?| $else_block )?
+                }
+            })),
+            || $f,
+        ) {
+            Ok(v) => v,
+            Err(exit) => $crate::rsexcept!(@replay exit $jumps),
+        }
+    };
+    (
+        @build $mode:ident $unwind:ident $jumps:tt $body:tt
+        catch { $($arms:tt)* }
+        $( else |$v:ident $(: $vt:ty)?| $else_block:block )?
+    ) => {
+        match $crate::__private::rethrowing(
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                #[allow(unused_imports)]
+                use $crate::__private::Question as _;
+                $crate::rsexcept! {
+                    @flow $mode $unwind $body
+                    catch { $($arms)* }
+                    $( else |$v $(: $vt)?| $else_block )?
+                }
+            })),
+        ) {
+            Ok(v) => v,
+            Err(exit) => $crate::rsexcept!(@replay exit $jumps),
+        }
+    };
+    (
+        @flow once $unwind:ident { $($body:tt)* }
+        catch { $($arms:tt)* }
+        $( else |$v:ident $(: $vt:ty)?| $else_block:block )?
+    ) => {
+        match $crate::rsexcept!(@try $unwind $($body)*) {
+            Ok(Ok(v)) => Ok($crate::rsexcept!(@else v $( |$v $(: $vt)?| $else_block )?)),
+            Ok(Err(exit)) => Err(exit),
+            Err(e) => $crate::rsexcept!(@handle Ok e; $($arms)*),
+        }
+    };
+    (
+        @flow retry $unwind:ident { $($body:tt)* }
+        catch { $($arms:tt)* }
+        $( else |$v:ident $(: $vt:ty)?| $else_block:block )?
+    ) => {
         {
-            let old_hook = std::panic::take_hook();
-            std::panic::set_hook(Box::new(|_| {}));
-            match std::panic::catch_unwind(|| $b) {
-                Ok(v) => v,
-                Err(e) => {
-                    std::panic::set_hook(old_hook);
-                    $(
-                        if let Some($p) = e.downcast_ref::<$t>(){
-                            $handler
-                        }
-                        else
-                    )*
-                    {
-                        std::panic::resume_unwind(e)
+            let mut attempt = 0;
+            // Only the catch arms run inside the loop, since a `retry` arm
+            // expands to `continue`.
+            let outcome = loop {
+                attempt += 1;
+                let _attempt = $crate::__private::enter_attempt(attempt);
+                break match $crate::rsexcept!(@try $unwind $($body)*) {
+                    Ok(flow) => Ok(flow),
+                    Err(e) => $crate::rsexcept!(@handle Err e; $($arms)*),
+                };
+            };
+            match outcome {
+                Ok(Ok(v)) => Ok($crate::rsexcept!(@else v $( |$v $(: $vt)?| $else_block )?)),
+                Ok(Err(exit)) => Err(exit),
+                Err(handled) => Ok(handled),
+            }
+        }
+    };
+    (@split $split:ident $handled:ident; $(,)?) => {};
+    (@split $split:ident $handled:ident; $t:ty, $p:pat => retry $($rest:tt)*) => {
+        compile_error!("`retry` isn't available in `catch*` blocks");
+    };
+    (@split $split:ident $handled:ident; _, $p:pat => $handler:expr $(,)?) => {
+        let members = $split.take_all();
+        if !members.is_empty() {
+            let $p = members;
+            $handled = ::core::option::Option::Some($handler);
+        }
+    };
+    (@split $split:ident $handled:ident; $t:ty, $p:pat => $handler:expr $(, $($rest:tt)*)?) => {
+        let members = $split.take::<$t>();
+        if !members.is_empty() {
+            let $p = members;
+            $handled = ::core::option::Option::Some($handler);
+        }
+        $crate::rsexcept!(@split $split $handled; $($($rest)*)?);
+    };
+    (@handle $wrap:ident $e:ident; $($arms:tt)*) => {
+        {
+            // Owns the payload and makes it the current exception while the
+            // arms run.
+            #[allow(unused_mut)]
+            let mut $e = $crate::__private::Handling::new($e);
+            #[allow(unused_imports)]
+            use $crate::__private::{LocalProbe as _, SyncProbe as _};
+            // Kept apart so that catch arms which all diverge don't warn
+            // about the unreachable `$wrap`.
+            #[allow(clippy::diverging_sub_expression)]
+            let _handled = $crate::rsexcept!(@catch $e; $($arms)*);
+            #[allow(unreachable_code)]
+            let result = ::core::result::Result::$wrap(_handled);
+            result
+        }
+    };
+    (@else $value:ident) => {
+        $value
+    };
+    (@else $value:ident |$v:ident $(: $vt:ty)?| $else_block:block) => {
+        {
+            let $v $(: $vt)? = $value;
+            $else_block
+        }
+    };
+    (@try $unwind:ident $($body:tt)*) => {
+        {
+            let _silence = $crate::__private::silence();
+            std::panic::catch_unwind($crate::__private::$unwind(|| -> ::core::result::Result<_, $crate::__private::Exit<_>> {
+                #[allow(unused_imports)]
+                use $crate::__private::{Question as _, Throw as _};
+                // Kept apart so that a diverging try block doesn't warn about
+                // the unreachable `Ok`.
+                #[allow(clippy::diverging_sub_expression)]
+                let _value = { $($body)* };
+                #[allow(unreachable_code)]
+                let result = ::core::result::Result::Ok(_value);
+                result
+            }))
+        }
+    };
+    (@replay $exit:ident [$( ($id:tt $kind:ident $($label:lifetime)?) )*]) => {
+        match $exit {
+            $crate::__private::Exit::Return(r) => return r,
+            $crate::__private::Exit::Jump(_id) => {
+                $(
+                    if _id == $id {
+                        $kind $($label)?;
                     }
+                )*
+                unreachable!()
+            }
+        }
+    };
+
+    // Rewrites the try block so that `return`, `break`, `continue` and `?`
+    // leave the closure with an `Exit` describing where to go instead.
+    // State: [frames] [jumps] [output] loop-state expr-start context input
+    //
+    // The context is `(question unwind (clauses) [labels])`, where
+    // `question` says what `?` does: `propagate` returns from the enclosing
+    // function and `throwing` panics with the error. `unwind` names the
+    // `__private` function the try block's closure is passed through, and
+    // `labels` lists the labels defined so far in the try block.
+    //
+    // The loop state is `out` outside of any loop nested in the try block,
+    // `pend` between a loop keyword and its body, and `inner` inside a
+    // nested loop, where unlabeled `break` and `continue` are left alone.
+    // Labeled ones are only left alone if the try block defines the label.
+    // Closures, `fn` items and `async` blocks are copied without rewriting.
+    (@rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($question:ident ($($next:tt)*) $labels:tt)) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*]
+        $flag:ident $start:ident $ctx:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps
+            [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* }))]
+            $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*]
+        $flag:ident $start:ident $ctx:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps
+            [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* }))]
+            $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt
+        [$($out:tt)*] $flag:ident $start:ident $ctx:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* ( $($out)* )] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt
+        [$($out:tt)*] $flag:ident $start:ident $ctx:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* [ $($out)* ]] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt
+        [$($out:tt)*] $flag:ident $start:ident $ctx:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* { $($out)* }] $fflag no $ctx $($rest)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[ret [$($out)*] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt $out:tt $flag:ident $start:ident ($q:ident $n:tt []) break $label:lifetime $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_jump $stack $jumps $out $flag ($q $n []) (break $label) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt $out:tt $flag:ident $start:ident ($q:ident $n:tt []) continue $label:lifetime $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_jump $stack $jumps $out $flag ($q $n []) (continue $label) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt $out:tt $flag:ident $start:ident ($q:ident $n:tt $labels:tt) break $label:lifetime $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_label ($) $labels $stack $jumps $out $flag ($q $n $labels) (break $label) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt $out:tt $flag:ident $start:ident ($q:ident $n:tt $labels:tt) continue $label:lifetime $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_label ($) $labels $stack $jumps $out $flag ($q $n $labels) (continue $label) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] inner $start:ident $ctx:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* break] inner no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] inner $start:ident $ctx:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* continue] inner no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt $out:tt $flag:ident $start:ident $ctx:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_jump $stack $jumps $out $flag $ctx (break) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt $out:tt $flag:ident $start:ident $ctx:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_jump $stack $jumps $out $flag $ctx (continue) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) ? $($rest:tt)*) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* .__rsexcept_question()?] $flag no $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident
+        ($q:ident $n:tt [$($labels:tt)*]) $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $label :] $flag no ($q $n [$($labels)* $label]) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] out $start:ident $ctx:tt loop $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* loop] pend no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] out $start:ident $ctx:tt while $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* while] pend no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] out $start:ident $ctx:tt for $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* for] pend no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt fn $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_skip $stack $jumps [$($out)* fn] $flag $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt async $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_skip $stack $jumps [$($out)* async] $flag $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt move | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_params $stack $jumps [$($out)* move |] $flag $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt move || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_closure $stack $jumps [$($out)* move ||] $flag $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident yes $ctx:tt | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_params $stack $jumps [$($out)* |] $flag $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident yes $ctx:tt || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_closure $stack $jumps [$($out)* ||] $flag $ctx $($rest)*)
+    };
+    // A `|` after one of these tokens starts a closure rather than an
+    // operator.
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt = | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* =] $flag yes $ctx | $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt = || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* =] $flag yes $ctx || $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt , | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* ,] $flag yes $ctx | $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt , || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* ,] $flag yes $ctx || $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ; | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* ;] $flag yes $ctx | $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ; || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* ;] $flag yes $ctx || $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt => | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* =>] $flag yes $ctx | $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt => || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* =>] $flag yes $ctx || $($rest)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt { $($g:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt ( $($g:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt [ $($g:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*)
+    };
+    // A group of a single token is copied like a plain token, unless that
+    // token is `return`, `break`, `continue` or a group, since nothing else
+    // in it could need rewriting.
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { return } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { break } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { continue } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { ( $($g:tt)* ) } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* ))
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { [ $($g:tt)* ] } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ])
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt { { $($g:tt)* } } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* })
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( return ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( break ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( continue ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( ( $($g:tt)* ) ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* ))
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( [ $($g:tt)* ] ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ])
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt ( { $($g:tt)* } ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* })
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ return ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ break ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ continue ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ ( $($g:tt)* ) ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* ))
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ [ $($g:tt)* ] ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ])
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt [ { $($g:tt)* } ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)*] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* })
+    };
+    // The rules below copy up to ten plain tokens at once, to keep the
+    // recursion depth down for long try blocks. For each number of plain
+    // tokens, they handle the end of the input, `return`, `?` and groups
+    // right away, and stop in front of any other token with a rule above.
+    (@rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 )] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 ]] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 }] $fflag no $ctx $($rest)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt return $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[ret [$($out)* $t0] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 .__rsexcept_question()?] $flag no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx break $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx continue $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt loop $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx loop $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt while $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx while $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt for $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx for $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt fn $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx fn $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt async $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx async $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $label:lifetime : $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx $label : $($rest)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt { $($g:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt ( $($g:tt)* ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt [ $($g:tt)* ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)* $t0] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { return } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { break } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { continue } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* ))
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ])
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* })
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( return ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( break ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( continue ) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* ))
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ])
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* })
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ return ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ break ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (@rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ continue ] $($rest:tt)*) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (@rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 )] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 ]] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 }] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[ret [$($out)* $t0 $t1] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 .__rsexcept_question()?] $flag no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt break $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx break $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt continue $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx continue $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt loop $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx loop $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt while $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx while $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt for $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx for $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt fn $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx fn $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt async $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0 $t1] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0 $t1] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[bracket [$($out)* $t0 $t1] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $s:tt | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx $s | $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $s:tt || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 )] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 ]] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 }] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[ret [$($out)* $t0 $t1 $t2] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ? $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 .__rsexcept_question()?] $flag no $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx break $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx continue $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt loop $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx while $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt for $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx for $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt fn $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $s:tt | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx $s | $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $s:tt || $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 $t3 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 $t3 )] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 $t3 ]] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 $t3 }] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw [[ret [$($out)* $t0 $t1 $t2 $t3] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 .__rsexcept_question()?] $flag no $ctx $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx break $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx continue $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt loop $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx while $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt for $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx for $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt fn $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 $t3 $t4 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 $t3 $t4 )] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 $t3 $t4 ]] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt
+    ) => {
+        $crate::rsexcept!(@rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 $t3 $t4 }] $fflag no $ctx $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[ret [$($out)* $t0 $t1 $t2 $t3 $t4] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 .__rsexcept_question()?] $flag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx break $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx continue $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt loop $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx while $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt for $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx for $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt fn $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 $t3 $t4 $t5 )] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 ]] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 }] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[ret [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 .__rsexcept_question()?] $flag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx break $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx continue $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt loop $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx while $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt for $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx for $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt fn $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 )] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 ]] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 }] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[ret [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 .__rsexcept_question()?] $flag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx break $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx continue $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt loop $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx while $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt for $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx for $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt fn $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 )] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 ]] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 }] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[ret [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 .__rsexcept_question()?] $flag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx break $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx continue $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt loop $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx while $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt for $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx for $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt fn $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx async $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx $label : $($rest)*)
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw [] $jumps:tt [$($out:tt)*] $flag:ident $start:ident ($q:ident ($($next:tt)*) $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt
+    ) => {
+        $crate::rsexcept!($($next)* $jumps { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 })
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $(; $($rest:tt)*)?
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 }))] $fflag no $ctx $(; $($rest)*)?
+        )
+    };
+    (
+        @rw [[ret [$($fout:tt)*] $fflag:ident] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt , $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* return ::core::result::Result::Err($crate::__private::Exit::Return({ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 }))] $fflag no $ctx , $($rest)*
+        )
+    };
+    (
+        @rw [[paren [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* ( $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 )] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[bracket [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* [ $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 ]] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [[brace [$($fout:tt)*] $fflag:ident [$($rest:tt)*]] $($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt
+    ) => {
+        $crate::rsexcept!(
+            @rw [$($stack)*] $jumps [$($fout)* { $($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 }] $fflag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt return $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[ret [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag] $($stack)*] $jumps [] $flag yes $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident (throwing $n:tt $labels:tt) $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 .__rsexcept_throw()] $flag no (throwing $n $labels) $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ? $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 .__rsexcept_question()?] $flag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt break $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx break $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt continue $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx continue $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt loop $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx loop $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt while $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx while $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt for $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx for $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt fn $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx fn $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt async $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx async $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $label:lifetime : $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx $label : $($rest)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] out [$($rest)*]] $($stack)*] $jumps [] inner yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] pend $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] pend [$($rest)*]] $($stack)*] $jumps [] out yes $ctx $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { $g0:tt $g1:tt $($g:tt)* } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { return } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { break } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { continue } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { ( $($g:tt)* ) } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { [ $($g:tt)* ] } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt { { $($g:tt)* } } $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[brace [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( $g0:tt $g1:tt $($g:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( return ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( break ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( continue ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( ( $($g:tt)* ) ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( [ $($g:tt)* ] ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt ( { $($g:tt)* } ) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[paren [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ $g0:tt $g1:tt $($g:tt)* ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx $g0 $g1 $($g)*
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ return ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx return
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ break ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx break
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ continue ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx continue
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ ( $($g:tt)* ) ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx ( $($g)* )
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ [ $($g:tt)* ] ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx [ $($g)* ]
+        )
+    };
+    (
+        @rw [$($stack:tt)*] $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt [ { $($g:tt)* } ] $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw [[bracket [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag [$($rest)*]] $($stack)*] $jumps [] $flag yes $ctx { $($g)* }
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $flag no $ctx $s || $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $s:tt | $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx $s | $($rest)*)
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $s:tt || $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8] $flag no $ctx $s || $($rest)*
+        )
+    };
+    (
+        @rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $t9:tt $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 $t9] $flag no $ctx $($rest)*)
+    };
+    (@rw $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $start:ident $ctx:tt $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $t] $flag no $ctx $($rest)*)
+    };
+    (@rw_jump $stack:tt [] [$($out:tt)*] $flag:ident $ctx:tt ($($jump:tt)*) $($rest:tt)*) => {
+        $crate::rsexcept!(
+            @rw $stack [((0) $($jump)*)]
+            [$($out)* return ::core::result::Result::Err($crate::__private::Exit::Jump(0))]
+            $flag no $ctx $($rest)*
+        )
+    };
+    (
+        @rw_jump $stack:tt [($id:tt $($last:tt)*) $($older:tt)*] [$($out:tt)*] $flag:ident $ctx:tt
+        ($($jump:tt)*) $($rest:tt)*
+    ) => {
+        $crate::rsexcept!(
+            @rw $stack [(($id + 1) $($jump)*) ($id $($last)*) $($older)*]
+            [$($out)* return ::core::result::Result::Err($crate::__private::Exit::Jump($id + 1))]
+            $flag no $ctx $($rest)*
+        )
+    };
+    // Decides whether a labeled jump names a label the try block defines, by
+    // matching the label against the list in a local macro. `$d` is a `$`
+    // for writing that macro's rules.
+    (
+        @rw_label ($d:tt) [$($labels:lifetime)*] $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt
+        ($kind:ident $label:lifetime) $($rest:tt)*
+    ) => {
+        {
+            macro_rules! __rsexcept_label {
+                $(
+                    ($labels $d($d args:tt)*) => {
+                        $crate::rsexcept!(@rw_keep $d($d args)*)
+                    };
+                )*
+                ($d other:lifetime $d($d args:tt)*) => {
+                    $crate::rsexcept!(@rw_jump $d($d args)*)
+                };
+            }
+            __rsexcept_label!($label $stack $jumps $out $flag $ctx ($kind $label) $($rest)*)
+        }
+    };
+    (@rw_keep $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt ($($jump:tt)*) $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* $($jump)*] $flag no $ctx $($rest)*)
+    };
+    (@rw_skip $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt { $($g:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* { $($g)* }] $flag no $ctx $($rest)*)
+    };
+    (@rw_skip $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt ; $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* ;] $flag yes $ctx $($rest)*)
+    };
+    (@rw_skip $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt) => {
+        $crate::rsexcept!(@rw $stack $jumps $out $flag no $ctx)
+    };
+    (@rw_skip $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_skip $stack $jumps [$($out)* $t] $flag $ctx $($rest)*)
+    };
+    (@rw_params $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt | $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_closure $stack $jumps [$($out)* |] $flag $ctx $($rest)*)
+    };
+    (@rw_params $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt) => {
+        $crate::rsexcept!(@rw $stack $jumps $out $flag no $ctx)
+    };
+    (@rw_params $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_params $stack $jumps [$($out)* $t] $flag $ctx $($rest)*)
+    };
+    (@rw_closure $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt { $($g:tt)* } $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps [$($out)* { $($g)* }] $flag no $ctx $($rest)*)
+    };
+    (@rw_closure $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt -> $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_skip $stack $jumps [$($out)* ->] $flag $ctx $($rest)*)
+    };
+    (@rw_closure $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_closure_expr $stack $jumps $out $flag $ctx $($rest)*)
+    };
+    (@rw_closure_expr $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt) => {
+        $crate::rsexcept!(@rw $stack $jumps $out $flag no $ctx)
+    };
+    (@rw_closure_expr $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt , $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps $out $flag no $ctx , $($rest)*)
+    };
+    (@rw_closure_expr $stack:tt $jumps:tt $out:tt $flag:ident $ctx:tt ; $($rest:tt)*) => {
+        $crate::rsexcept!(@rw $stack $jumps $out $flag no $ctx ; $($rest)*)
+    };
+    (@rw_closure_expr $stack:tt $jumps:tt [$($out:tt)*] $flag:ident $ctx:tt $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@rw_closure_expr $stack $jumps [$($out)* $t] $flag $ctx $($rest)*)
+    };
+    // Warns about each arm after an unguarded `dyn Family` arm whose type is
+    // a member of the family.
+    (@hides $family:path; $(,)?) => {};
+    (@hides $family:path; move $($rest:tt)*) => {
+        $crate::rsexcept!(@hides $family; $($rest)*)
+    };
+    (@hides $family:path; mut $($rest:tt)*) => {
+        $crate::rsexcept!(@hides $family; $($rest)*)
+    };
+    (@hides $family:path; _, $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hides $family:path; message, $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hides $family:path; Exception, $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hides $family:path; dyn $other:path, $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hides $family:path; $t:ty | $($rest:tt)*) => {
+        $crate::rsexcept!(@hidden $family; $t);
+        $crate::rsexcept!(@hides $family; $($rest)*)
+    };
+    (@hides $family:path; $t:ty, $($rest:tt)*) => {
+        $crate::rsexcept!(@hidden $family; $t);
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hides $family:path; $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hidden $family:path; $t:ty) => {
+        #[allow(unused_imports)]
+        use $crate::__private::{Hidden as _, Reachable as _};
+        (&&$crate::__private::HiddenBy::<$t, dyn $family>::default()).check();
+    };
+    (@hides_skip $family:path; => $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_handler $family; $($rest)*)
+    };
+    (@hides_skip $family:path; $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_skip $family; $($rest)*)
+    };
+    (@hides_skip $family:path;) => {};
+    (@hides_handler $family:path; , $($rest:tt)*) => {
+        $crate::rsexcept!(@hides $family; $($rest)*)
+    };
+    (@hides_handler $family:path; $t:tt $($rest:tt)*) => {
+        $crate::rsexcept!(@hides_handler $family; $($rest)*)
+    };
+    (@hides_handler $family:path;) => {};
+    (@retry $e:ident $(, $max:expr)?) => {
+        {
+            if true $( && $crate::attempt() < $max )? {
+                continue;
+            }
+            std::panic::resume_unwind($e.take())
+        }
+    };
+    (@catch $e:ident; $(,)?) => {
+        std::panic::resume_unwind($e.take())
+    };
+    (@catch $e:ident; _, $p:pat => retry $(max $max:expr)?, $($rest:tt)+) => {
+        compile_error!("the catch-all arm `_, e => ...` must be the last arm")
+    };
+    (@catch $e:ident; _, $p:pat $(if $guard:expr)? => retry $(max $max:expr)? $(, $($rest:tt)*)?) => {
+        match $e.payload() {
+            $p $(if $guard)? => $crate::rsexcept!(@retry $e $(, $max)?),
+            #[allow(unreachable_patterns)]
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; message, $p:pat $(if $guard:expr)? => retry $(max $max:expr)? $(, $($rest:tt)*)?) => {
+        match $crate::panic_message($e.payload()) {
+            Some($p) $(if $guard)? => $crate::rsexcept!(@retry $e $(, $max)?),
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; move $($t:ty)|+, $p:pat $(if $guard:expr)? => retry $($rest:tt)*) => {
+        compile_error!("`retry` is only supported in `T, pattern => retry`, `message, pattern => retry` and `_, pattern => retry` arms")
+    };
+    (@catch $e:ident; mut $t:ty, $p:pat $(if $guard:expr)? => retry $($rest:tt)*) => {
+        compile_error!("`retry` is only supported in `T, pattern => retry`, `message, pattern => retry` and `_, pattern => retry` arms")
+    };
+    (@catch $e:ident; Exception, $p:pat $(if $guard:expr)? => retry $($rest:tt)*) => {
+        compile_error!("`retry` is only supported in `T, pattern => retry`, `message, pattern => retry` and `_, pattern => retry` arms")
+    };
+    (@catch $e:ident; dyn $family:path, $p:pat $(if $guard:expr)? => retry $($rest:tt)*) => {
+        compile_error!("`retry` is only supported in `T, pattern => retry`, `message, pattern => retry` and `_, pattern => retry` arms")
+    };
+    (@catch $e:ident; $t:ty, $p:pat, $m:pat $(if $guard:expr)? => retry $($rest:tt)*) => {
+        compile_error!("`retry` is only supported in `T, pattern => retry`, `message, pattern => retry` and `_, pattern => retry` arms")
+    };
+    (@catch $e:ident; $($t:ty)|+ as $as:ty, $p:pat $(if $guard:expr)? => retry $($rest:tt)*) => {
+        compile_error!("`retry` is only supported in `T, pattern => retry`, `message, pattern => retry` and `_, pattern => retry` arms")
+    };
+    (@catch $e:ident; _, $p:pat if $guard:expr => $handler:expr $(, $($rest:tt)*)?) => {
+        match $e.take() {
+            $p if $guard => $handler,
+            payload => {
+                $e.put(payload);
+                $crate::rsexcept!(@catch $e; $($($rest)*)?)
+            }
+        }
+    };
+    (@catch $e:ident; _, $p:pat => $handler:expr $(,)?) => {
+        {
+            let $p: Box<dyn std::any::Any + Send + 'static> = $e.take();
+            $handler
+        }
+    };
+    (@catch $e:ident; _, $p:pat => $handler:expr, $($rest:tt)+) => {
+        compile_error!("the catch-all arm `_, e => ...` must be the last arm")
+    };
+    (@catch $e:ident; message, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match $crate::panic_message($e.payload()) {
+            Some($p) $(if $guard)? => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; Exception, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match $crate::__private::downcast_exception($e.payload()) {
+            Some($p) $(if $guard)? => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; dyn $family:path, $p:pat => $handler:expr $(, $($rest:tt)*)?) => {
+        {
+            $crate::rsexcept!(@hides $family; $($($rest)*)?);
+            $crate::rsexcept!(@catch $e; dyn $family, $p if true => $handler $(, $($rest)*)?)
+        }
+    };
+    (@catch $e:ident; dyn $family:path, $p:pat if $guard:expr => $handler:expr $(, $($rest:tt)*)?) => {
+        match $crate::__private::downcast_family::<dyn $family>($e.payload()) {
+            Some($p) if $guard => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; move $t:ty | $($ts:ty)|+, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::rsexcept!(@catch $e;
+            move $t, $p $(if $guard)? => $handler,
+            move $($ts)|+, $p $(if $guard)? => $handler
+            $(, $($rest)*)?
+        )
+    };
+    (@catch $e:ident; move $t:ty, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match match $crate::__private::downcast::<$t>($e.take()) {
+            Ok((v, shell)) => match v {
+                $p $(if $guard)? => Ok($handler),
+                #[allow(unreachable_patterns)]
+                v => Err(shell.rebox(v)),
+            },
+            Err(e) => Err(e),
+        } {
+            Ok(v) => v,
+            Err(payload) => {
+                $e.put(payload);
+                $crate::rsexcept!(@catch $e; $($($rest)*)?)
+            }
+        }
+    };
+    (@catch $e:ident; mut $t:ty, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match $crate::__private::downcast_mut::<$t>($e.payload_mut()) {
+            Some($p) $(if $guard)? => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; $t:ty, $p:pat, $m:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match $crate::__private::downcast_thrown::<$t>($e.payload()) {
+            Some(($p, $m)) $(if $guard)? => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; $t:ty, $p:pat $(if $guard:expr)? => retry $(max $max:expr)? $(, $($rest:tt)*)?) => {
+        match (&&$crate::__private::Probe::<$t>::default()).get($e.payload()) {
+            Some($p) $(if $guard)? => $crate::rsexcept!(@retry $e $(, $max)?),
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; $t:ty | $($ts:ty)|+, $p:pat $(if $guard:expr)? => retry $(max $max:expr)? $(, $($rest:tt)*)?) => {
+        $crate::rsexcept!(@catch $e;
+            $t, $p $(if $guard)? => retry $(max $max)?,
+            $($ts)|+, $p $(if $guard)? => retry $(max $max)?
+            $(, $($rest)*)?
+        )
+    };
+    (@catch $e:ident; $($t:ty)|+ as $as:ty, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match None::<&$as>
+            $( .or_else(|| (&&$crate::__private::Probe::<$t>::default()).get($e.payload()).map(|v| v as &$as)) )+
+        {
+            Some($p) $(if $guard)? => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+    (@catch $e:ident; $t:ty | $($ts:ty)|+, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        $crate::rsexcept!(@catch $e;
+            $t, $p $(if $guard)? => $handler,
+            $($ts)|+, $p $(if $guard)? => $handler
+            $(, $($rest)*)?
+        )
+    };
+    (@catch $e:ident; $t:ty, $p:pat $(if $guard:expr)? => $handler:expr $(, $($rest:tt)*)?) => {
+        match (&&$crate::__private::Probe::<$t>::default()).get($e.payload()) {
+            Some($p) $(if $guard)? => $handler,
+            _ => $crate::rsexcept!(@catch $e; $($($rest)*)?),
+        }
+    };
+}
+
+/// Runs the statements after the `;` as a try block and returns `Some` of
+/// their value, or `None` if they panicked with a payload of one of the
+/// listed types. Other panics propagate.
+///
+/// This is shorthand for a `flow try` block with a single arm, so panics
+/// are silenced and control flow is carried out the same way.
+/// # Examples
+/// ```
+/// use rsexcept::try_opt;
+/// let half = |n: i32| {
+///     try_opt!(&str | String;
+///         if n % 2 != 0 {
+///             panic!("{} is odd", n);
+///         }
+///         n / 2
+///     )
+/// };
+/// assert_eq!(half(8), Some(4));
+/// assert_eq!(half(7), None);
+/// ```
+#[macro_export]
+macro_rules! try_opt {
+    ($($t:ty)|+; $($body:tt)*) => {
+        $crate::rsexcept! {
+            flow try {
+                ::core::option::Option::Some({ $($body)* })
+            }
+            catch {
+                $($t)|+, _ => ::core::option::Option::None
+            }
+        }
+    };
+}
+
+/// Runs the block as a try block and aborts the process if any panic
+/// escapes it, after printing the panic message and where the block is.
+///
+/// Panics are silenced the same way as in `rsexcept!`, so only the abort
+/// report is printed. Since nothing runs after a panic, the block doesn't
+/// have to be unwind safe and may capture `&mut` references.
+/// # Examples
+/// ```
+/// use rsexcept::try_or_abort;
+/// let mut parsed = Vec::new();
+/// try_or_abort! {
+///     parsed.push("42".parse::<i32>().unwrap())
+/// };
+/// assert_eq!(parsed, [42]);
+/// ```
+#[macro_export]
+macro_rules! try_or_abort {
+    ($($body:tt)*) => {
+        $crate::rsexcept! {
+            unsafe_unwind try {
+                $($body)*
+            }
+            catch {
+                _, payload => $crate::__private::abort(
+                    payload,
+                    concat!(file!(), ":", line!(), ":", column!()),
+                )
+            }
+        }
+    };
+}
+
+/// Throws `value` wrapped in a [`Thrown`] envelope which records where,
+/// when and on which thread it was thrown, and the name of its type.
+///
+/// Catch arms for `T` match a thrown `T` as if it had been raised with
+/// `panic_any`. A `T, pattern, thrown => expr` arm only matches values
+/// thrown with `throw!`, and also binds the envelope as a `&Thrown`.
+/// # Examples
+/// ```
+/// use rsexcept::{rsexcept, throw};
+/// let line = rsexcept! {
+///     try {
+///         throw!(404u16)
+///     }
+///     catch {
+///         u16, 404, thrown => thrown.location().line()
+///     }
+/// };
+/// assert_eq!(line, line!() - 6);
+/// ```
+#[macro_export]
+macro_rules! throw {
+    (@from $value:expr, $cause:expr) => {
+        match $value {
+            value => {
+                #[allow(unused_imports)]
+                use $crate::__private::{ExceptionCast as _, PlainCast as _, SyncExceptionCast as _};
+                let exception = (&&&$crate::__private::CastFor::new(&value)).cast();
+                $crate::__private::throw(value, exception, $cause)
+            }
+        }
+    };
+    ($value:expr $(,)?) => {
+        $crate::throw!(@from $value, ::core::option::Option::None)
+    };
+}
+
+/// Throws `value` like [`throw!`], recording `cause`, the
+/// `Box<dyn Any + Send>` payload of a caught panic, as the reason it was
+/// thrown. [`chain`] walks from a payload through its causes, and uncaught
+/// values are reported along with every cause.
+/// # Examples
+/// ```
+/// use rsexcept::{rsexcept, throw_from, Thrown};
+/// let cause = rsexcept! {
+///     try {
+///         rsexcept! {
+///             try {
+///                 panic!("permission denied")
+///             }
+///             catch {
+///                 _, e => throw_from!(String::from("could not load config"), e)
+///             }
+///         }
+///     }
+///     catch {
+///         _, e => {
+///             let thrown = e.downcast_ref::<Thrown>().unwrap();
+///             rsexcept::panic_message(thrown.cause().unwrap()).unwrap().to_string()
+///         }
+///     }
+/// };
+/// assert_eq!(cause, "permission denied");
+/// ```
+#[macro_export]
+macro_rules! throw_from {
+    ($value:expr, $cause:expr $(,)?) => {
+        $crate::throw!(
+            @from $value,
+            ::core::option::Option::Some(::core::convert::identity::<
+                ::std::boxed::Box<dyn ::core::any::Any + ::core::marker::Send>,
+            >($cause))
+        )
+    };
+}
+
+/// Re-raises the panic the innermost catch arm of `rsexcept!` is handling,
+/// with its original payload, so a handler can log a panic and let it
+/// propagate unchanged. See [`current_exception`] for inspecting it.
+///
+/// `rethrow!()` must be called while an arm which borrows the payload is
+/// running, either in the arm itself or in a function it calls; in a
+/// `move` arm or the catch-all arm, which own the payload, use
+/// `resume_unwind` instead. In a `mut` arm it re-raises the payload with
+/// the arm's changes. Try blocks nested inside the arm don't catch the
+/// rethrown panic.
+/// # Examples
+/// ```
+/// use rsexcept::{rethrow, rsexcept};
+/// let n = rsexcept! {
+///     try {
+///         rsexcept! {
+///             try {
+///                 std::panic::panic_any(7u8)
+///             }
+///             catch {
+///                 u8, n => {
+///                     eprintln!("passing on {}", n);
+///                     rethrow!()
+///                 }
+///             }
+///         }
+///     }
+///     catch {
+///         u8, n => *n
+///     }
+/// };
+/// assert_eq!(n, 7);
+/// ```
+#[macro_export]
+macro_rules! rethrow {
+    () => {
+        $crate::__private::rethrow()
+    };
+}
+
+/// Declares exception types. Each declaration is a struct with named
+/// fields followed by a format string for its message, which can refer to
+/// the fields by name, and optionally an error code.
+///
+/// The struct derives `Debug`, and implements `Display` with the message and
+/// [`Exception`] with the struct's name as its name. Attributes are passed
+/// through to the struct. Implement [`Exception`] by hand for anything more
+/// involved, such as a `source`.
+///
+/// `Exception` arms of [`rsexcept!`] match declared types thrown with
+/// [`throw!`]. The declaration alone doesn't let them match a value raised
+/// with `panic_any`: call [`register_exception`] for the type first, for
+/// example at startup. `T` arms and `dyn Family` arms match either way.
+/// # Examples
+/// ```
+/// use rsexcept::{exception, rsexcept, throw};
+/// exception! {
+///     pub struct Timeout { ms: u64 } : "timed out after {ms}ms";
+///     pub struct NotFound { path: String } : "{path} not found", code = 404;
+/// }
+///
+/// let res = rsexcept! {
+///     try {
+///         throw!(NotFound { path: "/index.html".into() })
+///     }
+///     catch {
+///         Exception, e => format!("{}: {} ({:?})", e.name(), e.message(), e.code())
+///     }
+/// };
+/// assert_eq!(res, "NotFound: /index.html not found (Some(404))");
+/// assert_eq!(Timeout { ms: 5 }.to_string(), "timed out after 5ms");
+/// ```
+#[macro_export]
+macro_rules! exception {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident { $($fvis:vis $field:ident : $fty:ty),* $(,)? }
+        : $message:literal $(, code = $code:expr)?
+    );* $(;)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug)]
+            $vis struct $name {
+                $($fvis $field: $fty),*
+            }
+
+            impl ::core::fmt::Display for $name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #[allow(unused_variables)]
+                    let $name { $($field),* } = self;
+                    ::core::write!(f, $message)
+                }
+            }
+
+            impl $crate::Exception for $name {
+                fn name(&self) -> &'static str {
+                    ::core::stringify!($name)
+                }
+                $(
+                    fn code(&self) -> ::core::option::Option<i32> {
+                        ::core::option::Option::Some($code)
+                    }
+                )?
+            }
+        )*
+    };
+}
+
+/// Declares exception families. A family is a trait, implemented by each of
+/// the listed exception types, so that a single `dyn Family, e => expr`
+/// catch arm matches any member and binds it as a `&dyn Family`. A type may
+/// belong to several families.
+///
+/// Arms after an unguarded `dyn Family` arm whose type is a member of the
+/// family can never match, and trigger a deprecation warning.
+/// # Examples
+/// ```
+/// use rsexcept::{exception, exception_family, rsexcept, throw};
+/// exception! {
+///     pub struct Timeout { ms: u64 } : "timed out after {ms}ms";
+///     pub struct DnsFailure { host: String } : "could not resolve {host}";
+/// }
+/// exception_family! {
+///     pub NetworkError > { Timeout, DnsFailure }
+/// }
+///
+/// let res = rsexcept! {
+///     try {
+///         throw!(DnsFailure { host: "example.com".into() })
+///     }
+///     catch {
+///         dyn NetworkError, e => format!("network: {}", e.message())
+///     }
+/// };
+/// assert_eq!(res, "network: could not resolve example.com");
+/// ```
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// use rsexcept::{exception, exception_family, rsexcept, throw};
+/// exception! {
+///     pub struct Timeout { ms: u64 } : "timed out after {ms}ms";
+/// }
+/// exception_family! {
+///     pub NetworkError > { Timeout }
+/// }
+///
+/// fn main() {
+///     rsexcept! {
+///         try {
+///             throw!(Timeout { ms: 5 })
+///         }
+///         catch {
+///             dyn NetworkError, _ => (),
+///             // Already caught by the arm above.
+///             Timeout, _ => ()
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! exception_family {
+    ($($vis:vis $family:ident > { $($member:ty),* $(,)? });* $(;)?) => {
+        $(
+            $vis trait $family: $crate::Exception {}
+
+            impl $crate::__private::Family for dyn $family {
+                fn cast(
+                    value: &(dyn ::core::any::Any + ::core::marker::Send),
+                ) -> ::core::option::Option<&Self> {
+                    #[allow(unused_imports)]
+                    use $crate::__private::{LocalProbe as _, SyncProbe as _};
+                    $(
+                        if let ::core::option::Option::Some(v) =
+                            (&&$crate::__private::Probe::<$member>::default()).get(value)
+                        {
+                            return ::core::option::Option::Some(v);
+                        }
+                    )*
+                    ::core::option::Option::None
+                }
+            }
+
+            $(
+                impl $family for $member {}
+                impl $crate::__private::Member<dyn $family> for $member {}
+            )*
+        )*
+    };
+}
+
+#[cfg(test)]
+#[allow(unreachable_code)]
+mod tests {
+    use std::any::Any;
+    use std::panic::{panic_any, resume_unwind, AssertUnwindSafe, UnwindSafe};
+
+    /// Returns a try block body which raises `payload` again.
+    pub(crate) fn raising<T>(payload: Box<dyn Any + Send>) -> impl FnOnce() -> T + UnwindSafe {
+        let payload = AssertUnwindSafe(payload);
+        move || resume_unwind(payload.0)
+    }
+    #[test]
+    #[should_panic]
+    fn empty() {
+        rsexcept! {
+            try {
+                panic_any("Nothing should catch this")
+            }
+            catch {
+
+            }
+        }
+    }
+    #[test]
+    fn no_panic() {
+        let res = rsexcept! {
+            try {
+                86
+            }
+            catch {
+                i32, i => i + 12
+            }
+        };
+        assert_eq!(res, 86);
+    }
+    #[test]
+    fn one_arm() {
+        let res = rsexcept! {
+            try {
+                panic_any("Catch me if you can");
+                52
+            }
+            catch {
+                &str, _ => 42
+            }
+        };
+        assert_eq!(42, res);
+    }
+    #[test]
+    fn multi_arm() {
+        let res = rsexcept! {
+            try {
+                panic_any(6.54);
+                52
+            }
+            catch {
+                i32, _ => panic!("Nope!"),
+                &f64, _ => 65,
+                f64, _ => 42,
+                &str, _ => 87
+            }
+        };
+        assert_eq!(42, res);
+    }
+    #[test]
+    fn multi_arm_comma() {
+        let res = rsexcept! {
+            try {
+                panic_any(6.54);
+                52
+            }
+            catch {
+                i32, _ => panic!("Nope!"),
+                &f64, _ => 65,
+                f64, _ => 42,
+                &str, _ => 87, // terminal comma
+            }
+        };
+        assert_eq!(42, res);
+    }
+    #[test]
+    #[allow(clippy::redundant_static_lifetimes)]
+    fn patterns() {
+        static ARR: [&'static str; 5] = ["hey", "this", "is", "a", "array"];
+        let res = rsexcept! {
+            try {
+                panic_any(&ARR[1..]);
+                "Uh-oh, somersault jump!".to_string()
+            }
+            catch {
+                i32, _ => panic!("Nope!"),
+                &str, s => s.to_string(),
+                &[&str], ["uh", s, ..] => s.to_string(),
+                &[&str], ["this", h, t @ ..] => {
+                    format!("{}_{}", h, t[1])
+                }
+            }
+        };
+        assert_eq!("is_array", res);
+    }
+    #[test]
+    fn block() {
+        let res = rsexcept! {
+            try {
+                panic_any(62);
+                "62".to_string()
+            }
+            catch {
+                i32, i => {
+                    let i = i + 2;
+                    i.to_string()
+                }
+            }
+        };
+        assert_eq!("64", res);
+    }
+    #[test]
+    #[should_panic]
+    fn panic_in_handler() {
+        rsexcept! {
+            try {
+                panic_any(62);
+                ()
+            }
+            catch {
+                i32, _ => {
+                    panic!("Catch me")
+                }
+            }
+        };
+    }
+    #[test]
+    fn panic_propagate() {
+        let res = rsexcept! {
+            try {
+                rsexcept! {
+                    try {
+                        panic_any(62)
+                    }
+                    catch {
+                        i32, _ => {
+                            panic!("Catch me")
+                        }
+                    }
+                }
+            }
+            catch {
+                &str, s => format!("\"{}\"? Caught you!", s)
+            }
+        };
+        assert_eq!(res, "\"Catch me\"? Caught you!");
+    }
+    #[test]
+    fn concurrent() {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    (0..100)
+                        .map(|j| {
+                            rsexcept! {
+                                try {
+                                    if j % 2 == 0 {
+                                        panic_any(i * j)
+                                    }
+                                    i * j
+                                }
+                                catch {
+                                    i32, n => -n
+                                }
+                            }
+                        })
+                        .sum::<i32>()
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), 50 * i as i32);
+        }
+    }
+    #[test]
+    fn silenced_per_thread() {
+        use std::sync::{Arc, Barrier};
+        let barrier = Arc::new(Barrier::new(2));
+        let inner = Arc::clone(&barrier);
+        let inside = std::thread::spawn(move || {
+            rsexcept! {
+                try {
+                    let silenced = crate::is_silenced();
+                    inner.wait();
+                    inner.wait();
+                    silenced
+                }
+                catch {
+                    &str, _ => false
+                }
+            }
+        });
+        barrier.wait();
+        let outside = std::thread::spawn(crate::is_silenced).join().unwrap();
+        barrier.wait();
+        assert!(inside.join().unwrap());
+        assert!(!outside);
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn hook_restored_after_ok() {
+        rsexcept! {
+            try {
+                assert!(crate::is_silenced());
+            }
+            catch {
+                &str, _ => ()
+            }
+        };
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn hook_restored_after_catch() {
+        rsexcept! {
+            try {
+                panic_any(1)
+            }
+            catch {
+                i32, _ => assert!(!crate::is_silenced())
+            }
+        };
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn hook_restored_after_handler_panic() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(1)
+                }
+                catch {
+                    i32, _ => panic_any("handler")
+                }
+            }
+        });
+        assert!(res.is_err());
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn hook_restored_after_propagate() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(1)
+                }
+                catch {
+                    &str, _ => ()
+                }
+            }
+        });
+        assert!(res.is_err());
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn hook_restored_nested() {
+        rsexcept! {
+            try {
+                rsexcept! {
+                    try {
+                        panic_any(1)
+                    }
+                    catch {
+                        i32, _ => assert!(crate::is_silenced())
+                    }
+                };
+                assert!(crate::is_silenced());
+                panic_any("outer")
+            }
+            catch {
+                &str, _ => assert!(!crate::is_silenced())
+            }
+        };
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn finally_ok() {
+        let mut ran = false;
+        let res = rsexcept! {
+            try {
+                5
+            }
+            catch {
+                i32, _ => 0
+            }
+            finally {
+                ran = true;
+            }
+        };
+        assert_eq!(res, 5);
+        assert!(ran);
+    }
+    #[test]
+    fn finally_caught() {
+        let mut ran = false;
+        let res = rsexcept! {
+            try {
+                panic_any(5)
+            }
+            catch {
+                i32, i => i + 1
+            }
+            finally {
+                ran = true;
+            }
+        };
+        assert_eq!(res, 6);
+        assert!(ran);
+    }
+    #[test]
+    fn finally_unmatched() {
+        let mut ran = false;
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rsexcept! {
+                try {
+                    panic_any(5)
+                }
+                catch {
+                    &str, _ => ()
+                }
+                finally {
+                    ran = true;
+                }
+            }
+        }));
+        assert_eq!(res.unwrap_err().downcast_ref::<i32>(), Some(&5));
+        assert!(ran);
+    }
+    #[test]
+    fn finally_handler_panic() {
+        let mut ran = false;
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rsexcept! {
+                try {
+                    panic_any(5)
+                }
+                catch {
+                    i32, _ => panic_any("handler")
+                }
+                finally {
+                    ran = true;
+                }
+            }
+        }));
+        assert_eq!(res.unwrap_err().downcast_ref::<&str>(), Some(&"handler"));
+        assert!(ran);
+    }
+    #[test]
+    fn finally_panic_suppressed() {
+        let res = rsexcept! {
+            try {
+                rsexcept! {
+                    try {
+                        panic_any(5)
+                    }
+                    catch {
+                        &str, _ => 0
+                    }
+                    finally {
+                        panic_any("finally");
+                    }
+                }
+            }
+            catch {
+                i32, i => *i
+            }
+        };
+        assert_eq!(res, 5);
+        let suppressed = crate::take_suppressed();
+        assert_eq!(suppressed.len(), 1);
+        assert_eq!(suppressed[0].downcast_ref::<&str>(), Some(&"finally"));
+        assert!(crate::take_suppressed().is_empty());
+    }
+    #[test]
+    fn finally_suppressed_forgotten() {
+        let suppress = || {
+            let res = std::panic::catch_unwind(|| {
+                rsexcept! {
+                    try {
+                        panic_any(5)
+                    }
+                    catch {}
+                    finally {
+                        panic_any("finally");
+                    }
+                }
+            });
+            assert!(res.is_err());
+        };
+        suppress();
+        suppress();
+        assert_eq!(crate::take_suppressed().len(), 1);
+        suppress();
+        rsexcept! {
+            try {}
+            catch {}
+        };
+        assert!(crate::take_suppressed().is_empty());
+    }
+    #[test]
+    fn finally_panic_after_ok() {
+        let res = rsexcept! {
+            try {
+                rsexcept! {
+                    try {
+                        5
+                    }
+                    catch {
+                        &str, _ => 0
+                    }
+                    finally {
+                        panic_any("finally");
+                    }
+                }
+            }
+            catch {
+                &str, s => s.len() as i32
+            }
+        };
+        assert_eq!(res, 7);
+    }
+    #[test]
+    fn else_ok() {
+        let res = rsexcept! {
+            try {
+                20
+            }
+            catch {
+                i32, _ => "caught".to_string()
+            }
+            else |v| {
+                (v + 1).to_string()
+            }
+        };
+        assert_eq!(res, "21");
+    }
+    #[test]
+    fn else_skipped_on_panic() {
+        let res = rsexcept! {
+            try {
+                panic_any(20)
+            }
+            catch {
+                i32, i => i * 2
+            }
+            else |v: i32| {
+                v + 1
+            }
+        };
+        assert_eq!(res, 40);
+    }
+    #[test]
+    fn else_panic_not_caught() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    20
+                }
+                catch {
+                    i32, _ => 0
+                }
+                else |_v| {
+                    panic_any(1)
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<i32>(), Some(&1));
+    }
+    #[test]
+    fn else_finally() {
+        let mut order = Vec::new();
+        rsexcept! {
+            try {
+                "try"
+            }
+            catch {
+                i32, _ => ()
+            }
+            else |v| {
+                order.push(v);
+                order.push("else")
+            }
+            finally {
+                order.push("finally")
+            }
+        };
+        assert_eq!(order, ["try", "else", "finally"]);
+    }
+    #[test]
+    fn catch_all() {
+        let res = rsexcept! {
+            try {
+                panic_any(6.54);
+                "none".to_string()
+            }
+            catch {
+                i32, _ => "i32".to_string(),
+                _, e => format!("{:?}", e.downcast::<f64>().unwrap()),
+            }
+        };
+        assert_eq!(res, "6.54");
+    }
+    #[test]
+    fn catch_all_after_match() {
+        let res = rsexcept! {
+            try {
+                panic_any(6)
+            }
+            catch {
+                i32, i => *i,
+                _, _ => 0
+            }
+        };
+        assert_eq!(res, 6);
+    }
+    #[test]
+    fn move_arm() {
+        let res = rsexcept! {
+            try {
+                panic_any(String::from("owned"));
+                String::new()
+            }
+            catch {
+                i32, _ => String::new(),
+                move String, s => s
+            }
+        };
+        assert_eq!(res, "owned");
+    }
+    #[test]
+    fn move_arm_passes_on() {
+        #[derive(Debug, PartialEq)]
+        struct MyError {
+            code: u32,
+        }
+        let res = rsexcept! {
+            try {
+                panic_any(MyError { code: 3 })
+            }
+            catch {
+                move String, _ => None,
+                move MyError, MyError { code: 4 } => None,
+                move MyError, e => Some(e)
+            }
+        };
+        assert_eq!(res, Some(MyError { code: 3 }));
+    }
+    #[test]
+    fn move_arm_unmatched() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(5u8)
+                }
+                catch {
+                    move String, _ => ()
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<u8>(), Some(&5));
+    }
+    #[test]
+    fn guards() {
+        let status = |code: i32| {
+            rsexcept! {
+                try {
+                    panic_any(code)
+                }
+                catch {
+                    i32, n if *n >= 500 => "server",
+                    i32, n if *n >= 400 => "client",
+                    &str, s if s.contains("timeout") => "timeout",
+                    i32, _ => "other"
+                }
+            }
+        };
+        assert_eq!(status(503), "server");
+        assert_eq!(status(404), "client");
+        assert_eq!(status(302), "other");
+    }
+    #[test]
+    fn guard_unmatched() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any("connection reset")
+                }
+                catch {
+                    &str, s if s.contains("timeout") => ()
+                }
+            }
+        });
+        assert_eq!(
+            res.unwrap_err().downcast_ref::<&str>(),
+            Some(&"connection reset")
+        );
+    }
+    #[test]
+    fn guard_move_and_catch_all() {
+        let res = rsexcept! {
+            try {
+                panic_any(String::from("timeout"))
+            }
+            catch {
+                move String, s if s.len() > 10 => s,
+                _, e if e.is::<i32>() => String::new(),
+                move String, s => s.to_uppercase()
+            }
+        };
+        assert_eq!(res, "TIMEOUT");
+    }
+    #[test]
+    fn multi_type() {
+        let describe = |payload| {
+            let raise = raising(payload);
+            rsexcept! {
+                try {
+                    raise()
+                }
+                catch {
+                    i32 | i64 | u32, n => n.to_string(),
+                    &str, s => s.to_string()
+                }
+            }
+        };
+        assert_eq!(describe(Box::new(1i32)), "1");
+        assert_eq!(describe(Box::new(2i64)), "2");
+        assert_eq!(describe(Box::new(3u32)), "3");
+        assert_eq!(describe(Box::new("four")), "four");
+    }
+    #[test]
+    fn multi_type_dyn() {
+        use std::fmt::Display;
+        let describe = |payload| {
+            let raise = raising(payload);
+            rsexcept! {
+                try {
+                    raise()
+                }
+                catch {
+                    i32 | String | &str as dyn Display, d if d.to_string() != "skip" => {
+                        format!("<{}>", d)
+                    },
+                    move String | &str, _ => "skipped".to_string()
+                }
+            }
+        };
+        assert_eq!(describe(Box::new(1i32)), "<1>");
+        assert_eq!(describe(Box::new(String::from("two"))), "<two>");
+        assert_eq!(describe(Box::new("three")), "<three>");
+        assert_eq!(describe(Box::new("skip")), "skipped");
+    }
+    #[test]
+    fn message_literal() {
+        let res = rsexcept! {
+            try {
+                panic!("literal")
+            }
+            catch {
+                message, s => s.to_string()
+            }
+        };
+        assert_eq!(res, "literal");
+    }
+    #[test]
+    fn message_formatted() {
+        let n = 4;
+        let res = rsexcept! {
+            try {
+                panic!("formatted {}", n)
+            }
+            catch {
+                &str, _ => String::new(),
+                message, s if s.starts_with("formatted") => s.to_uppercase()
+            }
+        };
+        assert_eq!(res, "FORMATTED 4");
+    }
+    #[test]
+    fn message_other_payload() {
+        let res = rsexcept! {
+            try {
+                panic_any(4)
+            }
+            catch {
+                message, _ => 0,
+                i32, n => *n
+            }
+        };
+        assert_eq!(res, 4);
+    }
+    #[test]
+    fn long_try_block() {
+        let step = |n: i32| n + 1;
+        let res = rsexcept! {
+            try {
+                let v0 = step(1);
+                let v1 = step(v0) * 2 - v0;
+                let v2 = step(v1) * 2 - v1;
+                let v3 = step(v2) * 2 - v2;
+                let v4 = step(v3) * 2 - v3;
+                let v5 = step(v4) * 2 - v4;
+                let v6 = step(v5) * 2 - v5;
+                let v7 = step(v6) * 2 - v6;
+                let v8 = step(v7) * 2 - v7;
+                let v9 = step(v8) * 2 - v8;
+                let v10 = step(v9) * 2 - v9;
+                let v11 = step(v10) * 2 - v10;
+                let v12 = step(v11) * 2 - v11;
+                let v13 = step(v12) * 2 - v12;
+                let v14 = step(v13) * 2 - v13;
+                let v15 = step(v14) * 2 - v14;
+                let v16 = step(v15) * 2 - v15;
+                let v17 = step(v16) * 2 - v16;
+                let v18 = step(v17) * 2 - v17;
+                let v19 = step(v18) * 2 - v18;
+                let v20 = step(v19) * 2 - v19;
+                let v21 = step(v20) * 2 - v20;
+                let v22 = step(v21) * 2 - v21;
+                let v23 = step(v22) * 2 - v22;
+                let v24 = step(v23) * 2 - v23;
+                let v25 = step(v24) * 2 - v24;
+                let v26 = step(v25) * 2 - v25;
+                let v27 = step(v26) * 2 - v26;
+                let v28 = step(v27) * 2 - v27;
+                let v29 = step(v28) * 2 - v28;
+                let v30 = step(v29) * 2 - v29;
+                let v31 = step(v30) * 2 - v30;
+                let v32 = step(v31) * 2 - v31;
+                let v33 = step(v32) * 2 - v32;
+                let v34 = step(v33) * 2 - v33;
+                let v35 = step(v34) * 2 - v34;
+                let v36 = step(v35) * 2 - v35;
+                let v37 = step(v36) * 2 - v36;
+                let v38 = step(v37) * 2 - v37;
+                let v39 = step(v38) * 2 - v38;
+                let v40 = step(v39) * 2 - v39;
+                let v41 = step(v40) * 2 - v40;
+                let v42 = step(v41) * 2 - v41;
+                let v43 = step(v42) * 2 - v42;
+                let v44 = step(v43) * 2 - v43;
+                let v45 = step(v44) * 2 - v44;
+                let v46 = step(v45) * 2 - v45;
+                let v47 = step(v46) * 2 - v46;
+                let v48 = step(v47) * 2 - v47;
+                let v49 = step(v48) * 2 - v48;
+                let v50 = step(v49) * 2 - v49;
+                let v51 = step(v50) * 2 - v50;
+                let v52 = step(v51) * 2 - v51;
+                let v53 = step(v52) * 2 - v52;
+                let v54 = step(v53) * 2 - v53;
+                let v55 = step(v54) * 2 - v54;
+                let v56 = step(v55) * 2 - v55;
+                let v57 = step(v56) * 2 - v56;
+                let v58 = step(v57) * 2 - v57;
+                let v59 = step(v58) * 2 - v58;
+                let v60 = step(v59) * 2 - v59;
+                let v61 = step(v60) * 2 - v60;
+                let v62 = step(v61) * 2 - v61;
+                let v63 = step(v62) * 2 - v62;
+                let v64 = step(v63) * 2 - v63;
+                let v65 = step(v64) * 2 - v64;
+                let v66 = step(v65) * 2 - v65;
+                let v67 = step(v66) * 2 - v66;
+                let v68 = step(v67) * 2 - v67;
+                let v69 = step(v68) * 2 - v68;
+                let v70 = step(v69) * 2 - v69;
+                let v71 = step(v70) * 2 - v70;
+                let v72 = step(v71) * 2 - v71;
+                let v73 = step(v72) * 2 - v72;
+                let v74 = step(v73) * 2 - v73;
+                let v75 = step(v74) * 2 - v74;
+                let v76 = step(v75) * 2 - v75;
+                let v77 = step(v76) * 2 - v76;
+                let v78 = step(v77) * 2 - v77;
+                step(v78) * 2 - v78
+            }
+            catch {
+                i32, _ => 0
+            }
+        };
+        assert_eq!(res, 160);
+    }
+    #[test]
+    fn long_flow_try_block() {
+        fn run(input: &str) -> Result<i32, std::num::ParseIntError> {
+            let step = |n: i32| n + 1;
+            let res = rsexcept! {
+                flow try {
+                    let v0 = input.parse::<i32>()?;
+                    let v1 = step(v0) * 2 - v0;
+                    let v2 = step(v1) * 2 - v1;
+                    let v3 = step(v2) * 2 - v2;
+                    let v4 = step(v3) * 2 - v3;
+                    let v5 = step(v4) * 2 - v4;
+                    let v6 = step(v5) * 2 - v5;
+                    let v7 = step(v6) * 2 - v6;
+                    let v8 = step(v7) * 2 - v7;
+                    let v9 = step(v8) * 2 - v8;
+                    let v10 = step(v9) * 2 - v9;
+                    let v11 = step(v10) * 2 - v10;
+                    let v12 = step(v11) * 2 - v11;
+                    let v13 = step(v12) * 2 - v12;
+                    let v14 = step(v13) * 2 - v13;
+                    let v15 = step(v14) * 2 - v14;
+                    let v16 = step(v15) * 2 - v15;
+                    let v17 = step(v16) * 2 - v16;
+                    let v18 = step(v17) * 2 - v17;
+                    let v19 = step(v18) * 2 - v18;
+                    let v20 = step(v19) * 2 - v19;
+                    let v21 = step(v20) * 2 - v20;
+                    let v22 = step(v21) * 2 - v21;
+                    let v23 = step(v22) * 2 - v22;
+                    let v24 = step(v23) * 2 - v23;
+                    let v25 = step(v24) * 2 - v24;
+                    let v26 = step(v25) * 2 - v25;
+                    let v27 = step(v26) * 2 - v26;
+                    let v28 = step(v27) * 2 - v27;
+                    let v29 = step(v28) * 2 - v28;
+                    let v30 = step(v29) * 2 - v29;
+                    let v31 = step(v30) * 2 - v30;
+                    let v32 = step(v31) * 2 - v31;
+                    let v33 = step(v32) * 2 - v32;
+                    let v34 = step(v33) * 2 - v33;
+                    let v35 = step(v34) * 2 - v34;
+                    let v36 = step(v35) * 2 - v35;
+                    let v37 = step(v36) * 2 - v36;
+                    let v38 = step(v37) * 2 - v37;
+                    let v39 = step(v38) * 2 - v38;
+                    let v40 = step(v39) * 2 - v39;
+                    let v41 = step(v40) * 2 - v40;
+                    let v42 = step(v41) * 2 - v41;
+                    let v43 = step(v42) * 2 - v42;
+                    let v44 = step(v43) * 2 - v43;
+                    let v45 = step(v44) * 2 - v44;
+                    let v46 = step(v45) * 2 - v45;
+                    let v47 = step(v46) * 2 - v46;
+                    let v48 = step(v47) * 2 - v47;
+                    step(v48) * 2 - v48
+                }
+                catch {
+                    i32, _ => 0
+                }
+            };
+            Ok(res)
+        }
+        assert_eq!(run("1"), Ok(99));
+        assert!(run("x").is_err());
+    }
+    #[test]
+    fn control_flow_return() {
+        fn first_big(v: &[i32]) -> i32 {
+            rsexcept! {
+                flow try {
+                    for x in v {
+                        if *x > 2 {
+                            return *x * 10;
+                        }
+                    }
+                    0
+                }
+                catch {
+                    i32, _ => -1
+                }
+            }
+        }
+        assert_eq!(first_big(&[1, 2, 3, 4]), 30);
+        assert_eq!(first_big(&[1]), 0);
+    }
+    #[test]
+    fn control_flow_return_finally() {
+        fn early(flag: &std::cell::Cell<bool>) -> &'static str {
+            let res = rsexcept! {
+                flow try {
+                    if true {
+                        return "returned";
+                    }
+                    "fell through"
+                }
+                catch {
+                    &str, s => *s
+                }
+                finally {
+                    flag.set(true);
+                }
+            };
+            res
+        }
+        let flag = std::cell::Cell::new(false);
+        assert_eq!(early(&flag), "returned");
+        assert!(flag.get());
+    }
+    #[test]
+    fn control_flow_break_continue() {
+        let mut total = 0;
+        for i in 0..10 {
+            total += rsexcept! {
+                flow try {
+                    if i == 5 {
+                        break;
+                    }
+                    if i % 2 == 0 {
+                        continue;
+                    }
+                    i
+                }
+                catch {
+                    i32, _ => 0
+                }
+            };
+        }
+        assert_eq!(total, 1 + 3);
+    }
+    #[test]
+    fn control_flow_labels() {
+        let mut visited = Vec::new();
+        'outer: for i in 0..3 {
+            for j in 0..3 {
+                rsexcept! {
+                    flow try {
+                        if j == 1 {
+                            continue 'outer;
+                        }
+                        if i == 2 {
+                            break 'outer
+                        }
+                    }
+                    catch {
+                        i32, _ => ()
+                    }
+                };
+                visited.push((i, j));
+            }
+        }
+        assert_eq!(visited, [(0, 0), (1, 0)]);
+    }
+    #[test]
+    fn control_flow_labels_from_nested_loop() {
+        let mut visited = Vec::new();
+        'outer: for i in 0..3 {
+            rsexcept! {
+                unsafe_unwind flow try {
+                    for j in 0..3 {
+                        if j == 1 {
+                            continue 'outer;
+                        }
+                        if i == 2 {
+                            break 'outer;
+                        }
+                        visited.push((i, j));
+                    }
+                }
+                catch {
+                    i32, _ => ()
+                }
+            };
+            visited.push((i, 9));
+        }
+        assert_eq!(visited, [(0, 0), (1, 0)]);
+    }
+    #[test]
+    fn control_flow_nested_loops() {
+        let res = rsexcept! {
+            flow try {
+                let mut n = 0;
+                'inner: loop {
+                    while n < 10 {
+                        n += 1;
+                        if n == 3 {
+                            continue;
+                        }
+                        if n == 7 {
+                            break 'inner;
+                        }
+                    }
+                }
+                let add = |x: i32| {
+                    return x + 1;
+                };
+                add(n)
+            }
+            catch {
+                i32, _ => 0
+            }
+        };
+        assert_eq!(res, 8);
+    }
+    #[test]
+    fn control_flow_question() {
+        fn double(s: &str) -> Result<i32, String> {
+            let n = rsexcept! {
+                flow try {
+                    let parsed: Result<i32, String> = (|| {
+                        let n: i32 = s.parse().map_err(|_| format!("bad: {}", s))?;
+                        Ok(n)
+                    })();
+                    parsed? * 2
+                }
+                catch {
+                    i32, _ => 0
+                }
+            };
+            Ok(n)
+        }
+        assert_eq!(double("21"), Ok(42));
+        assert_eq!(double("x"), Err("bad: x".to_string()));
+    }
+    #[test]
+    fn control_flow_question_option() {
+        fn first_char(s: &str) -> Option<char> {
+            rsexcept! {
+                flow try {
+                    let c = s.chars().next()?;
+                    Some(c.to_ascii_uppercase())
+                }
+                catch {
+                    char, c => Some(*c)
+                }
+            }
+        }
+        assert_eq!(first_char("abc"), Some('A'));
+        assert_eq!(first_char(""), None);
+    }
+    #[test]
+    fn control_flow_in_arms() {
+        use std::cell::Cell;
+        use std::num::ParseIntError;
+        fn sum(items: &[i32], cleanups: &Cell<i32>) -> Result<i32, ParseIntError> {
+            let mut total = 0;
+            for &n in items {
+                let value = rsexcept! {
+                    throwing try {
+                        if n > 9 {
+                            panic_any(n)
+                        }
+                        n
+                    }
+                    catch {
+                        i32, 99 => break,
+                        i32, 50 => return Ok(-1),
+                        i32, 10 => continue,
+                        i32, _ => "x".parse::<i32>()?,
+                    }
+                    else |n| {
+                        if n == 0 {
+                            return Ok(total);
+                        }
+                        n
+                    }
+                    finally {
+                        cleanups.set(cleanups.get() + 1);
+                    }
+                };
+                total += value;
+            }
+            Ok(total)
+        }
+        let cleanups = Cell::new(0);
+        assert_eq!(sum(&[1, 10, 2, 99, 3], &cleanups), Ok(3));
+        assert_eq!(cleanups.replace(0), 4);
+        assert_eq!(sum(&[1, 50], &cleanups), Ok(-1));
+        assert_eq!(sum(&[1, 0, 2], &cleanups), Ok(1));
+        assert!(sum(&[11], &cleanups).is_err());
+        assert_eq!(cleanups.get(), 5);
+        // Unlabeled jumps in the arms of a block with a `retry` arm act on
+        // the enclosing loop, not the retry loop.
+        let mut seen = Vec::new();
+        for i in 0..3 {
+            seen.push(rsexcept! {
+                flow try {
+                    if i == 1 && crate::attempt() < 2 {
+                        panic_any("flaky")
+                    }
+                    if i == 2 {
+                        panic_any(i)
+                    }
+                    i
+                }
+                catch {
+                    &str, _ => retry,
+                    i32, _ => break,
+                }
+            });
+        }
+        assert_eq!(seen, [0, 1]);
+    }
+    #[test]
+    fn throwing_question() {
+        use std::num::ParseIntError;
+        let parse = |s: &str| {
+            rsexcept! {
+                throwing try {
+                    let n: i32 = s.parse()?;
+                    n * 2
+                }
+                catch {
+                    ParseIntError, e => {
+                        assert_eq!(e.to_string(), "invalid digit found in string");
+                        -1
+                    }
+                }
+            }
+        };
+        assert_eq!(parse("21"), 42);
+        assert_eq!(parse("x"), -1);
+    }
+    #[test]
+    fn throwing_question_unmatched() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                throwing try {
+                    Err::<(), _>(String::from("boom"))?
+                }
+                catch {
+                    &str, _ => ()
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<String>().unwrap(), "boom");
+    }
+    #[test]
+    fn retry() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let calls = AtomicUsize::new(0);
+        let res = rsexcept! {
+            try {
+                calls.fetch_add(1, Ordering::SeqCst);
+                if crate::attempt() < 3 {
+                    panic_any("flaky");
+                }
+                crate::attempt()
+            }
+            catch {
+                &str, _ => retry
+            }
+        };
+        assert_eq!(res, 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(crate::attempt(), 0);
+    }
+    #[test]
+    fn retry_max() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let calls = AtomicUsize::new(0);
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    panic_any(crate::attempt())
+                }
+                catch {
+                    usize, n if *n == 10 => 0,
+                    usize, _ => retry max 3,
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<usize>(), Some(&3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+    #[test]
+    fn retry_guard() {
+        let res = rsexcept! {
+            try {
+                panic_any(crate::attempt())
+            }
+            catch {
+                usize, n if *n < 4 => retry,
+                usize, n => *n * 10
+            }
+        };
+        assert_eq!(res, 40);
+    }
+    #[test]
+    fn retry_message() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic!("attempt {}", crate::attempt())
+                }
+                catch {
+                    message, m if m.ends_with('1') => retry,
+                    message, _ => retry max 3,
+                }
+            }
+        });
+        assert_eq!(
+            res.unwrap_err().downcast_ref::<String>().unwrap(),
+            "attempt 3"
+        );
+    }
+    #[test]
+    fn retry_catch_all() {
+        let res = rsexcept! {
+            try {
+                if crate::attempt() < 3 {
+                    panic_any(crate::attempt())
                 }
+                crate::attempt()
             }
-        }
-    };
-}
-
-#[cfg(test)]
-#[allow(unreachable_code)]
-mod tests {
-    use std::panic::panic_any;
+            catch {
+                _, p if p.is::<usize>() => retry,
+                _, _ => 0
+            }
+        };
+        assert_eq!(res, 3);
+    }
     #[test]
-    #[should_panic]
-    fn empty() {
-        rsexcept! {
+    fn retry_after_many_arms() {
+        let res = rsexcept! {
             try {
-                panic_any("Nothing should catch this")
+                if crate::attempt() < 3 {
+                    panic_any(crate::attempt() as u64)
+                }
+                crate::attempt()
             }
             catch {
-
+                i8, _ => 0,
+                i16, _ => 0,
+                i32, _ => 0,
+                i64, _ => 0,
+                i128, _ => 0,
+                u8, _ => 0,
+                u16, _ => 0,
+                u32, _ => 0,
+                u128, _ => 0,
+                f32, _ => 0,
+                f64, _ => 0,
+                char, _ => 0,
+                bool, _ => 0,
+                &str, _ => 0,
+                String, _ => 0,
+                u64, _ => retry
             }
-        }
+        };
+        assert_eq!(res, 3);
     }
     #[test]
-    fn no_panic() {
+    fn unsafe_unwind_mut_capture() {
+        let mut log = Vec::new();
         let res = rsexcept! {
-            try {
-                86
+            unsafe_unwind try {
+                log.push("try");
+                panic_any(5)
             }
             catch {
-                i32, i => i + 12
+                i32, n => *n
             }
         };
-        assert_eq!(res, 86);
+        log.push("after");
+        assert_eq!(res, 5);
+        assert_eq!(log, ["try", "after"]);
     }
     #[test]
-    fn one_arm() {
+    fn unsafe_unwind_throwing() {
+        use std::cell::RefCell;
+        let seen = RefCell::new(Vec::new());
+        let parse = |s: &str| {
+            rsexcept! {
+                unsafe_unwind throwing try {
+                    seen.borrow_mut().push(s.to_string());
+                    s.parse::<i32>()?
+                }
+                catch {
+                    std::num::ParseIntError, _ => -1
+                }
+            }
+        };
+        assert_eq!(parse("7"), 7);
+        assert_eq!(parse("x"), -1);
+        assert_eq!(*seen.borrow(), ["7", "x"]);
+    }
+    #[test]
+    fn try_opt() {
+        assert_eq!(try_opt!(i32; 5), Some(5));
+        assert_eq!(try_opt!(u8 | i32; panic_any(3i32)), None::<()>);
+        let res = std::panic::catch_unwind(|| try_opt!(i32; panic_any("other")));
+        assert_eq!(res.unwrap_err().downcast_ref::<&str>(), Some(&"other"));
+    }
+    #[test]
+    fn try_opt_return() {
+        fn first_even(ns: &[i32]) -> Option<i32> {
+            for &n in ns {
+                try_opt!(&str;
+                    if n % 2 == 0 {
+                        return Some(n);
+                    }
+                )?;
+            }
+            None
+        }
+        assert_eq!(first_even(&[1, 4, 6]), Some(4));
+        assert_eq!(first_even(&[1, 3]), None);
+    }
+    #[test]
+    fn try_or_abort() {
+        if std::env::var_os("RSEXCEPT_ABORT_CHILD").is_some() {
+            try_or_abort! {
+                panic!("fatal {}", 7)
+            }
+        }
+        assert_eq!(try_or_abort! { 1 + 1 }, 2);
+        let mut v = vec![1];
+        try_or_abort! { v.push(2) };
+        assert_eq!(v, [1, 2]);
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::try_or_abort", "--nocapture"])
+            .env("RSEXCEPT_ABORT_CHILD", "1")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("panic escaped try_or_abort! at src/lib.rs:"));
+        assert!(stderr.contains(": fatal 7"));
+        assert!(!stderr.contains("panicked at"));
+    }
+    #[test]
+    fn throw_caught_by_type() {
         let res = rsexcept! {
             try {
-                panic_any("Catch me if you can");
-                52
+                throw!(5i32)
             }
             catch {
-                &str, _ => 42
+                u8, _ => 0,
+                i32 | i64 as dyn std::fmt::Display, n => n.to_string().parse::<i32>().unwrap() + 1,
             }
         };
-        assert_eq!(42, res);
+        assert_eq!(res, 6);
+        let res = rsexcept! {
+            try {
+                throw!("thrown")
+            }
+            catch {
+                message, m => m.len()
+            }
+        };
+        assert_eq!(res, 6);
     }
     #[test]
-    fn multi_arm() {
+    fn throw_metadata() {
+        let line = line!() + 5;
+        let res = std::thread::Builder::new()
+            .name("thrower".into())
+            .spawn(|| {
+                rsexcept! {
+                    try { throw!(String::from("bad")) }
+                    catch {
+                        String, s, thrown => (
+                            s.clone(),
+                            thrown.type_name(),
+                            thrown.thread_name().map(String::from),
+                            thrown.location().line(),
+                            thrown.location().file(),
+                        )
+                    }
+                }
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(
+            res,
+            (
+                "bad".to_string(),
+                std::any::type_name::<String>(),
+                Some("thrower".to_string()),
+                line,
+                file!()
+            )
+        );
+    }
+    #[test]
+    fn metadata_arm_needs_throw() {
         let res = rsexcept! {
             try {
-                panic_any(6.54);
-                52
+                panic_any(1i32)
             }
             catch {
-                i32, _ => panic!("Nope!"),
-                &f64, _ => 65,
-                f64, _ => 42,
-                &str, _ => 87
+                i32, _, _ => "thrown",
+                i32, _ => "raised"
             }
         };
-        assert_eq!(42, res);
+        assert_eq!(res, "raised");
     }
     #[test]
-    fn multi_arm_comma() {
+    fn move_thrown_reboxed() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    throw!(String::from("keep"))
+                }
+                catch {
+                    move String, s if s.is_empty() => s
+                }
+            }
+        });
+        let payload = res.unwrap_err();
+        let thrown = payload.downcast_ref::<crate::Thrown>().unwrap();
+        assert_eq!(thrown.downcast_ref::<String>().unwrap(), "keep");
         let res = rsexcept! {
             try {
-                panic_any(6.54);
-                52
+                throw!(String::from("take"))
             }
             catch {
-                i32, _ => panic!("Nope!"),
-                &f64, _ => 65,
-                f64, _ => 42,
-                &str, _ => 87, // terminal comma
+                move String, s => s
             }
         };
-        assert_eq!(42, res);
+        assert_eq!(res, "take");
     }
     #[test]
-    fn patterns() {
-        static ARR: [&'static str; 5] = ["hey", "this", "is", "a", "array"];
+    fn exception_arm() {
+        exception! {
+            struct Timeout { ms: u64 } : "timed out after {ms}ms", code = 408;
+        }
+        let catch = |payload| {
+            let raise = raising(payload);
+            rsexcept! {
+                try {
+                    raise()
+                }
+                catch {
+                    Exception, e if e.code() == Some(408) => format!("{}: {}", e.name(), e),
+                    Exception, e => e.message(),
+                    _, _ => String::from("other")
+                }
+            }
+        };
+        let thrown = std::panic::catch_unwind(|| throw!(Timeout { ms: 30 })).unwrap_err();
+        assert_eq!(catch(thrown), "Timeout: timed out after 30ms");
+        let thrown = std::panic::catch_unwind(|| throw!(5u8)).unwrap_err();
+        assert_eq!(catch(thrown), "other");
+        assert_eq!(catch(Box::new(Timeout { ms: 1 })), "other");
+    }
+    #[test]
+    fn exception_registered() {
+        use crate::Exception;
+        exception! {
+            struct Refused { port: u16 } : "port {port} refused";
+        }
+        #[derive(Debug)]
+        struct Wrapped(Refused);
+        impl std::fmt::Display for Wrapped {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "wrapped")
+            }
+        }
+        impl Exception for Wrapped {
+            fn source(&self) -> Option<&dyn Exception> {
+                Some(&self.0)
+            }
+        }
+        crate::register_exception::<Wrapped>();
         let res = rsexcept! {
             try {
-                panic_any(&ARR[1..]);
-                "Uh-oh, somersault jump!".to_string()
+                panic_any(Wrapped(Refused { port: 80 }))
             }
             catch {
-                i32, _ => panic!("Nope!"),
-                &str, s => s.to_string(),
-                &[&str], ["uh", s, ..] => s.to_string(),
-                &[&str], ["this", h, t @ ..] => {
-                    format!("{}_{}", h, t[1])
+                Exception, e => (e.message(), e.source().map(|s| s.message()), e.name())
+            }
+        };
+        assert_eq!(res.0, "wrapped");
+        assert_eq!(res.1.as_deref(), Some("port 80 refused"));
+        assert!(res.2.ends_with("Wrapped"));
+    }
+    #[test]
+    fn exception_declared_and_registered() {
+        exception! {
+            struct Overloaded { load: u8 } : "load at {load}%", code = 503;
+        }
+        let catch = || {
+            rsexcept! {
+                try {
+                    panic_any(Overloaded { load: 98 })
+                }
+                catch {
+                    Exception, e => format!("{} {:?}: {}", e.name(), e.code(), e),
+                    _, _ => String::from("other")
                 }
             }
         };
-        assert_eq!("is_array", res);
+        assert_eq!(catch(), "other");
+        crate::register_exception::<Overloaded>();
+        assert_eq!(catch(), "Overloaded Some(503): load at 98%");
     }
     #[test]
-    fn block() {
+    fn exception_family() {
+        exception! {
+            struct Timeout { ms: u64 } : "timed out after {ms}ms";
+            struct Reset { peer: &'static str } : "reset by {peer}";
+            struct Denied { path: &'static str } : "cannot open {path}";
+        }
+        exception_family! {
+            NetworkError > { Timeout, Reset };
+            Retryable > { Timeout }
+        }
+        let catch = |payload| {
+            let raise = raising(payload);
+            rsexcept! {
+                try {
+                    raise()
+                }
+                catch {
+                    dyn Retryable, e if e.name() == "Reset" => unreachable!(),
+                    dyn NetworkError, e => format!("network: {}", e),
+                    Denied, d => d.to_string(),
+                }
+            }
+        };
+        let thrown = std::panic::catch_unwind(|| throw!(Timeout { ms: 3 })).unwrap_err();
+        assert_eq!(catch(thrown), "network: timed out after 3ms");
+        assert_eq!(
+            catch(Box::new(Reset { peer: "db" })),
+            "network: reset by db"
+        );
+        assert_eq!(catch(Box::new(Denied { path: "/etc" })), "cannot open /etc");
+        let res = std::panic::catch_unwind(|| catch(Box::new(7u8)));
+        assert_eq!(res.unwrap_err().downcast_ref::<u8>(), Some(&7));
+    }
+    #[test]
+    fn cause_chain() {
+        exception! {
+            struct ConfigLoadFailed { path: &'static str } : "could not load {path}";
+        }
+        let payload = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    rsexcept! {
+                        try {
+                            panic_any(13i32)
+                        }
+                        catch {
+                            _, e => throw_from!(String::from("read failed"), e)
+                        }
+                    }
+                }
+                catch {
+                    _, e => throw_from!(ConfigLoadFailed { path: "app.toml" }, e)
+                }
+            }
+        })
+        .unwrap_err();
+        let links: Vec<_> = crate::chain(&*payload).collect();
+        assert_eq!(links.len(), 3);
+        assert_eq!(
+            links[0].downcast_ref::<ConfigLoadFailed>().unwrap().path,
+            "app.toml"
+        );
+        assert_eq!(links[1].downcast_ref::<String>().unwrap(), "read failed");
+        assert_eq!(links[2].downcast_ref::<i32>(), Some(&13));
+        assert!(links[2].thrown().is_none());
+        let location = links[1].thrown().unwrap().location();
+        let mut report = Vec::new();
+        crate::chain::report(&mut report, &*payload).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        let mut lines = report.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .ends_with("ConfigLoadFailed: could not load app.toml"));
+        assert_eq!(
+            lines.next().unwrap(),
+            format!(
+                "caused by: alloc::string::String: read failed (at {})",
+                location
+            )
+        );
+        assert_eq!(lines.next().unwrap(), "caused by: Box<dyn Any>");
+        assert_eq!(lines.next(), None);
+    }
+    #[test]
+    fn suppressed_attached_to_thrown() {
+        let payload = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    rsexcept! {
+                        try {
+                            throw!(1u8)
+                        }
+                        catch {
+                            &str, _ => ()
+                        }
+                        finally {
+                            panic_any(2u16);
+                        }
+                    }
+                }
+                catch {
+                    u8, _, thrown if thrown.suppressed().len() == 2 => unreachable!()
+                }
+                finally {
+                    panic_any(3u32);
+                }
+            }
+        })
+        .unwrap_err();
+        let suppressed = crate::suppressed(&*payload);
+        assert_eq!(suppressed.len(), 2);
+        assert_eq!(suppressed[0].downcast_ref::<u16>(), Some(&2));
+        assert_eq!(suppressed[1].downcast_ref::<u32>(), Some(&3));
+        assert!(crate::take_suppressed().is_empty());
+        let mut report = Vec::new();
+        crate::chain::report(&mut report, &*payload).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.ends_with("u8\n  suppressed: Box<dyn Any>\n  suppressed: Box<dyn Any>\n"));
+    }
+    #[test]
+    fn catch_star() {
+        use crate::ExceptionGroup;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let group = || {
+            ExceptionGroup::new(vec![
+                Box::new(1i32),
+                Box::new(String::from("a")),
+                Box::new(2i32),
+                Box::new(3u8),
+            ])
+        };
         let res = rsexcept! {
             try {
-                panic_any(62);
-                "62".to_string()
+                panic_any(group())
+            }
+            catch* {
+                i32, ns => ns.iter().sum::<i32>(),
+                String, ss => ss.len() as i32,
+                _, rest => rest.len() as i32 * 10,
+            }
+        };
+        assert_eq!(res, 10);
+        let cleaned_up = AtomicUsize::new(0);
+        let payload = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    throw!(group())
+                }
+                catch* {
+                    i32, ns => assert_eq!(ns, [1, 2]),
+                    f64, _ => unreachable!(),
+                }
+                finally {
+                    cleaned_up.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        })
+        .unwrap_err();
+        assert_eq!(cleaned_up.load(Ordering::SeqCst), 1);
+        let thrown = payload.downcast_ref::<crate::Thrown>().unwrap();
+        let rest = thrown.downcast_ref::<ExceptionGroup>().unwrap();
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest.members()[0].downcast_ref::<String>().unwrap(), "a");
+        assert_eq!(rest.members()[1].downcast_ref::<u8>(), Some(&3));
+    }
+    #[test]
+    fn catch_star_single() {
+        let res = rsexcept! {
+            try {
+                panic_any(4i32)
+            }
+            catch* {
+                i32, ns => ns[0]
+            }
+        };
+        assert_eq!(res, 4);
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(4i32)
+                }
+                catch* {
+                    u8, _ => ()
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<i32>(), Some(&4));
+        let res = rsexcept! {
+            try {
+                5
+            }
+            catch* {
+                i32, _ => 0
+            }
+        };
+        assert_eq!(res, 5);
+    }
+    #[test]
+    fn rethrow_keeps_payload() {
+        let (n, line) = rsexcept! {
+            try {
+                rsexcept! {
+                    try {
+                        throw!(7u8)
+                    }
+                    catch {
+                        i32, _ => (0, 0),
+                        u8, 7 => rethrow!()
+                    }
+                }
             }
             catch {
-                i32, i => {
-                    let i = i + 2;
-                    i.to_string()
+                u8, n, thrown => (*n, thrown.location().line())
+            }
+        };
+        assert_eq!((n, line), (7, line!() - 12));
+        assert!(crate::current_exception(|e| e.is_none()));
+    }
+    #[test]
+    fn rethrow_from_helper() {
+        fn log_and_rethrow(log: &mut Vec<String>, n: &u8) -> ! {
+            log.push(format!("passing on {}", n));
+            rethrow!()
+        }
+        let mut log = Vec::new();
+        let n = rsexcept! {
+            unsafe_unwind try {
+                rsexcept! {
+                    try {
+                        panic_any(7u8)
+                    }
+                    catch {
+                        u8, n => log_and_rethrow(&mut log, n)
+                    }
                 }
             }
+            catch {
+                u8, n => *n
+            }
         };
-        assert_eq!("64", res);
+        assert_eq!(n, 7);
+        assert_eq!(log, ["passing on 7"]);
     }
     #[test]
-    #[should_panic]
-    fn panic_in_handler() {
-        rsexcept! {
+    fn rethrow_with_finally() {
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(String::from("x"))
+                }
+                catch {
+                    String, s if s == "x" => rethrow!()
+                }
+                finally {
+                    crate::current_exception(|e| assert!(e.is_none()));
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<String>().unwrap(), "x");
+    }
+    #[test]
+    fn current_exception_nested() {
+        let inner = |e: Option<&(dyn std::any::Any + Send)>| {
+            e.and_then(|e| e.downcast_ref::<i32>().copied())
+        };
+        let res = rsexcept! {
             try {
-                panic_any(62);
-                ()
+                panic_any(1i32)
             }
             catch {
                 i32, _ => {
-                    panic!("Catch me")
+                    let nested = rsexcept! {
+                        try {
+                            panic_any(2i32)
+                        }
+                        catch {
+                            i32, _ => crate::current_exception(inner),
+                        }
+                    };
+                    (nested, crate::current_exception(inner))
                 }
             }
         };
+        assert_eq!(res, (Some(2), Some(1)));
+        // The rethrown panic passes through try blocks nested in the arm.
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(1i32)
+                }
+                catch {
+                    i32, _ => rsexcept! {
+                        try {
+                            rethrow!()
+                        }
+                        catch {
+                            _, _ => 0
+                        }
+                    }
+                }
+            }
+        });
+        assert_eq!(res.unwrap_err().downcast_ref::<i32>(), Some(&1));
     }
     #[test]
-    fn panic_propagate() {
+    fn rethrow_outside_arm() {
         let res = rsexcept! {
+            try {
+                rethrow!()
+            }
+            catch {
+                message, m => m.to_string()
+            }
+        };
+        assert!(res.starts_with("rethrow!() must be used in a catch arm"));
+        let res = std::panic::catch_unwind(|| {
+            rsexcept! {
+                try {
+                    panic_any(3u8)
+                }
+                catch {
+                    move u8, _ => rethrow!()
+                }
+            }
+        });
+        assert!(crate::panic_message(&*res.unwrap_err())
+            .unwrap()
+            .starts_with("rethrow!() must be used in a catch arm"));
+    }
+    #[test]
+    fn mut_arm_rethrows_enriched() {
+        #[derive(Debug)]
+        struct Failed {
+            context: Vec<String>,
+        }
+        let layer = |id: u32| -> (Vec<String>, u32) {
+            rsexcept! {
+                try {
+                    throw!(Failed { context: Vec::new() })
+                }
+                catch {
+                    mut Failed, e => {
+                        e.context.push(format!("request {}", id));
+                        if id > 0 {
+                            rethrow!();
+                        }
+                        (Vec::new(), 0)
+                    }
+                }
+            }
+        };
+        let (context, line) = rsexcept! {
             try {
                 rsexcept! {
                     try {
-                        panic_any(62)
+                        layer(7)
                     }
                     catch {
-                        i32, _ => {
-                            panic!("Catch me")
+                        mut String, _ => (Vec::new(), 0),
+                        mut Failed, e if e.context.len() == 1 => {
+                            e.context.push(String::from("outer"));
+                            rethrow!()
                         }
                     }
                 }
             }
             catch {
-                &str, s => format!("\"{}\"? Caught you!", s)
+                Failed, e, thrown => (e.context.clone(), thrown.location().line())
             }
         };
-        assert_eq!(res, "\"Catch me\"? Caught you!");
+        assert_eq!(context, ["request 7", "outer"]);
+        assert_eq!(line, line!() - 33);
+    }
+    #[test]
+    fn mut_arm_handles() {
+        let res = rsexcept! {
+            try {
+                panic_any(String::from("a"))
+            }
+            catch {
+                mut String, s if s.is_empty() => 0,
+                mut String, s => {
+                    s.push('b');
+                    s.len()
+                }
+            }
+        };
+        assert_eq!(res, 2);
+        let shared = crate::ExceptionPtr::from_value(String::from("a"));
+        let res = rsexcept! {
+            try {
+                shared.clone().rethrow()
+            }
+            catch {
+                mut String, _ => String::new(),
+                String, s => s.clone()
+            }
+        };
+        assert_eq!(res, "a");
+    }
+    #[test]
+    fn uncaught_chain_report() {
+        if std::env::var_os("RSEXCEPT_REPORT_CHILD").is_some() {
+            rsexcept! {
+                try {
+                    panic!("disk full")
+                }
+                catch {
+                    _, e => throw_from!("could not save", e)
+                }
+            }
+        }
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::uncaught_chain_report", "--nocapture"])
+            .env("RSEXCEPT_REPORT_CHILD", "1")
+            .env("RUST_BACKTRACE", "0")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(stderr.matches("panicked at").count(), 1);
+        assert!(stderr.contains("&str: could not save\ncaused by: disk full\n"));
+    }
+    #[test]
+    fn throw_installs_hook() {
+        if std::env::var_os("RSEXCEPT_THROW_CHILD").is_some() {
+            throw!("before any try block");
+        }
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::throw_installs_hook", "--nocapture"])
+            .env("RSEXCEPT_THROW_CHILD", "1")
+            .env("RUST_BACKTRACE", "0")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("&str: before any try block\n"));
+    }
+    #[test]
+    fn group_report() {
+        let group = crate::ExceptionGroup::new(vec![Box::new("first"), Box::new(2u8)]);
+        assert_eq!(format!("{:?}", group), r#"["first", "Box<dyn Any>"]"#);
+        let mut report = Vec::new();
+        crate::chain::report(&mut report, &group).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(
+            report.ends_with("group of 2 exceptions\n  member: first\n  member: Box<dyn Any>\n")
+        );
     }
 }
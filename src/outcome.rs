@@ -0,0 +1,193 @@
+//! Caught panics as values.
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, UnwindSafe};
+
+use crate::hook::silence;
+use crate::payload::{downcast, downcast_ref, panic_message};
+
+/// Runs `body`, catching any panic it raises.
+///
+/// Panics inside the body are silenced the same way they are inside a
+/// `rsexcept!` try block.
+/// # Examples
+/// ```
+/// use rsexcept::run;
+/// let n = run(|| -> i32 { std::panic::panic_any(String::from("12")) })
+///     .recover::<String, _>(|s| s.parse().unwrap())
+///     .map(|n| n * 2)
+///     .rethrow();
+/// assert_eq!(n, 24);
+/// ```
+pub fn run<T, F: FnOnce() -> T + UnwindSafe>(body: F) -> Outcome<T> {
+    let _silence = silence();
+    match panic::catch_unwind(body) {
+        Ok(v) => Outcome::Ok(v),
+        Err(payload) => Outcome::Caught(Caught { payload }),
+    }
+}
+
+/// The result of [`run`]: either the body's value or the panic it raised.
+#[derive(Debug)]
+pub enum Outcome<T> {
+    /// The body returned normally.
+    Ok(T),
+    /// The body panicked.
+    Caught(Caught),
+}
+
+impl<T> Outcome<T> {
+    /// Handles a caught payload of type `E`, or an `E` thrown with `throw!`,
+    /// by taking it by value. Other payloads are left alone.
+    pub fn recover<E: Any + Send, F: FnOnce(E) -> T>(self, handler: F) -> Self {
+        match self {
+            Outcome::Caught(caught) => match downcast::<E>(caught.payload) {
+                Ok((e, _)) => Outcome::Ok(handler(e)),
+                Err(payload) => Outcome::Caught(Caught { payload }),
+            },
+            ok => ok,
+        }
+    }
+
+    /// Applies `f` to the value, if there is one.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Outcome<U> {
+        match self {
+            Outcome::Ok(v) => Outcome::Ok(f(v)),
+            Outcome::Caught(caught) => Outcome::Caught(caught),
+        }
+    }
+
+    /// Passes the value, if there is one, to `f` and returns its outcome.
+    pub fn and_then<U, F: FnOnce(T) -> Outcome<U>>(self, f: F) -> Outcome<U> {
+        match self {
+            Outcome::Ok(v) => f(v),
+            Outcome::Caught(caught) => Outcome::Caught(caught),
+        }
+    }
+
+    /// Turns both cases into a single value.
+    pub fn fold<U, F: FnOnce(T) -> U, G: FnOnce(Caught) -> U>(self, ok: F, caught: G) -> U {
+        match self {
+            Outcome::Ok(v) => ok(v),
+            Outcome::Caught(c) => caught(c),
+        }
+    }
+
+    /// Returns the value, or computes one from the caught panic.
+    pub fn get_or_else<F: FnOnce(Caught) -> T>(self, f: F) -> T {
+        self.fold(|v| v, f)
+    }
+
+    /// Returns the value, or re-raises the caught panic with its original
+    /// payload.
+    pub fn rethrow(self) -> T {
+        self.get_or_else(|caught| caught.rethrow())
+    }
+
+    /// Returns the caught payload if it is an `E`.
+    pub fn downcast_ref<E: Any>(&self) -> Option<&E> {
+        match self {
+            Outcome::Ok(_) => None,
+            Outcome::Caught(caught) => caught.downcast_ref(),
+        }
+    }
+}
+
+/// A panic caught by [`run`].
+pub struct Caught {
+    payload: Box<dyn Any + Send>,
+}
+
+impl Caught {
+    /// Returns the payload if it is an `E`.
+    pub fn downcast_ref<E: Any>(&self) -> Option<&E> {
+        downcast_ref(&*self.payload)
+    }
+
+    /// Returns the message if the payload is a `&'static str` or a `String`.
+    pub fn message(&self) -> Option<&str> {
+        panic_message(&*self.payload)
+    }
+
+    /// Takes the payload out.
+    pub fn into_payload(self) -> Box<dyn Any + Send> {
+        self.payload
+    }
+
+    /// Re-raises the panic with its original payload.
+    pub fn rethrow(self) -> ! {
+        panic::resume_unwind(self.payload)
+    }
+}
+
+impl fmt::Debug for Caught {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message() {
+            Some(message) => f.debug_tuple("Caught").field(&message).finish(),
+            None => f.write_str("Caught(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, Outcome};
+    use std::panic::{self, panic_any};
+
+    #[test]
+    fn no_panic() {
+        let res = run(|| 3).map(|n| n + 1).rethrow();
+        assert_eq!(res, 4);
+    }
+    #[test]
+    fn recover_by_type() {
+        let res = run(|| -> i32 { panic_any(5u8) })
+            .recover::<i32, _>(|_| 1)
+            .recover::<u8, _>(|n| n as i32 * 2)
+            .rethrow();
+        assert_eq!(res, 10);
+    }
+    #[test]
+    fn and_then_chains() {
+        let res = run(|| 2).and_then(|n| run(move || -> i32 { panic_any(n * 7) }));
+        assert_eq!(res.downcast_ref::<i32>(), Some(&14));
+        assert!(run(|| 1).downcast_ref::<i32>().is_none());
+    }
+    #[test]
+    fn fold_and_get_or_else() {
+        let caught = run(|| -> u32 { panic!("bad {}", 1) });
+        assert_eq!(format!("{:?}", caught), r#"Caught(Caught("bad 1"))"#);
+        let s = caught.fold(|n| n.to_string(), |c| c.message().unwrap().to_uppercase());
+        assert_eq!(s, "BAD 1");
+        let n = run(|| -> u32 { panic_any(()) }).get_or_else(|_| 9);
+        assert_eq!(n, 9);
+    }
+    #[test]
+    fn rethrow_keeps_payload() {
+        let res = panic::catch_unwind(|| {
+            run(|| panic_any(vec![1, 2]))
+                .recover::<String, _>(|_| ())
+                .map(|()| ())
+                .rethrow()
+        });
+        assert_eq!(
+            res.unwrap_err().downcast_ref::<Vec<i32>>(),
+            Some(&vec![1, 2])
+        );
+        assert!(!crate::is_silenced());
+    }
+    #[test]
+    fn into_payload() {
+        match run(|| panic_any(1.5f64)) {
+            Outcome::Caught(c) => assert_eq!(*c.into_payload().downcast::<f64>().unwrap(), 1.5),
+            Outcome::Ok(()) => panic!("expected a panic"),
+        }
+    }
+    #[test]
+    fn recover_thrown() {
+        let res = run(|| -> String { crate::throw!(String::from("x")) });
+        assert_eq!(res.downcast_ref::<String>().unwrap(), "x");
+        assert_eq!(res.recover::<String, _>(|s| s + "y").rethrow(), "xy");
+    }
+}
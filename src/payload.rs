@@ -0,0 +1,86 @@
+//! Helpers for inspecting panic payloads.
+
+use std::any::Any;
+
+use crate::exception_ptr::ExceptionPtr;
+use crate::thrown::Thrown;
+
+type Payload = Box<dyn Any + Send>;
+
+/// Returns the message of a panic payload created by `panic!`.
+///
+/// `panic!("literal")` panics with a `&'static str`, while
+/// `panic!("{}", x)` panics with a `String`. This accepts either one, and
+/// also looks inside a [`Thrown`] envelope and an [`ExceptionPtr`].
+pub fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    if let Some(ptr) = payload.downcast_ref::<ExceptionPtr>() {
+        if let Some(s) = ptr.downcast_ref::<&'static str>() {
+            Some(s)
+        } else {
+            ptr.downcast_ref::<String>().map(String::as_str)
+        }
+    } else if let Some(s) = downcast_ref::<&'static str>(payload) {
+        Some(s)
+    } else {
+        downcast_ref::<String>(payload).map(String::as_str)
+    }
+}
+
+/// Returns the payload if it is a `T`, or the value inside it if it is a
+/// [`Thrown`] envelope holding a `T`.
+pub fn downcast_ref<T: Any>(payload: &(dyn Any + Send)) -> Option<&T> {
+    payload.downcast_ref::<T>().or_else(|| {
+        payload
+            .downcast_ref::<Thrown>()
+            .and_then(Thrown::downcast_ref)
+    })
+}
+
+/// Returns the payload if it is a `T`, or the value inside it if it is a
+/// [`Thrown`] envelope holding a `T`, for changing it in place.
+pub fn downcast_mut<T: Any>(payload: &mut (dyn Any + Send)) -> Option<&mut T> {
+    if payload.is::<T>() {
+        return payload.downcast_mut();
+    }
+    payload.downcast_mut::<Thrown>()?.downcast_mut()
+}
+
+/// Returns the value inside a [`Thrown`] envelope holding a `T`, along with
+/// the envelope.
+pub fn downcast_thrown<T: Any>(payload: &(dyn Any + Send)) -> Option<(&T, &Thrown)> {
+    let thrown = payload.downcast_ref::<Thrown>()?;
+    Some((thrown.downcast_ref()?, thrown))
+}
+
+/// Takes a `T` out of the payload, or out of the [`Thrown`] envelope which
+/// holds it. The returned [`Shell`] puts a value back the way it was found.
+pub fn downcast<T: Any + Send>(payload: Payload) -> Result<(T, Shell), Payload> {
+    let payload = match payload.downcast::<T>() {
+        Ok(v) => return Ok((*v, Shell(None))),
+        Err(payload) => payload,
+    };
+    let mut thrown = payload.downcast::<Thrown>()?;
+    match thrown.take_value().downcast::<T>() {
+        Ok(v) => Ok((*v, Shell(Some(thrown)))),
+        Err(value) => {
+            thrown.set_value(value);
+            Err(thrown)
+        }
+    }
+}
+
+/// What is left of a payload after [`downcast`] took its value.
+pub struct Shell(Option<Box<Thrown>>);
+
+impl Shell {
+    /// Turns `value` back into the payload it was taken from.
+    pub fn rebox<T: Any + Send>(self, value: T) -> Payload {
+        match self.0 {
+            Some(mut thrown) => {
+                thrown.set_value(Box::new(value));
+                thrown
+            }
+            None => Box::new(value),
+        }
+    }
+}
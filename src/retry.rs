@@ -0,0 +1,34 @@
+//! Support code for `retry` arms.
+
+use std::cell::Cell;
+
+thread_local! {
+    /// Attempt number of the innermost try block with a `retry` arm.
+    static ATTEMPT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns the attempt number (starting at 1) of the innermost `rsexcept!`
+/// block with a `retry` arm that the current thread is executing, or 0 if
+/// there is none.
+pub fn attempt() -> usize {
+    ATTEMPT.with(Cell::get)
+}
+
+/// Sets the attempt number until the returned guard is dropped.
+pub fn enter_attempt(attempt: usize) -> Attempt {
+    let previous = ATTEMPT.with(|current| current.replace(attempt));
+    Attempt { previous }
+}
+
+/// Guard returned by [`enter_attempt`].
+#[must_use]
+pub struct Attempt {
+    previous: usize,
+}
+
+impl Drop for Attempt {
+    fn drop(&mut self) {
+        let previous = self.previous;
+        let _ = ATTEMPT.try_with(|current| current.set(previous));
+    }
+}
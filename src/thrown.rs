@@ -0,0 +1,145 @@
+//! The envelope `throw!` wraps thrown values in.
+
+use std::any::{self, Any};
+use std::fmt;
+use std::panic::{self, Location};
+use std::thread;
+use std::time::SystemTime;
+
+use crate::exception::{Cast, Exception};
+
+/// A value thrown with `throw!`, along with where and when it was thrown.
+///
+/// Catch arms see through the envelope, so a `T, pattern => expr` arm
+/// matches a `T` whether it was thrown with `throw!` or with
+/// `panic_any`. A `T, pattern, thrown => expr` arm also binds the
+/// envelope.
+pub struct Thrown {
+    value: Box<dyn Any + Send>,
+    type_name: &'static str,
+    location: &'static Location<'static>,
+    thread: Option<String>,
+    timestamp: SystemTime,
+    exception: Option<Cast>,
+    cause: Option<Box<dyn Any + Send>>,
+    suppressed: Vec<Box<dyn Any + Send>>,
+}
+
+impl Thrown {
+    /// Wraps `value`, recording the caller's location, the current thread
+    /// and the current time.
+    #[track_caller]
+    pub fn new<T: Any + Send>(value: T) -> Self {
+        Thrown {
+            value: Box::new(value),
+            type_name: any::type_name::<T>(),
+            location: Location::caller(),
+            thread: thread::current().name().map(String::from),
+            timestamp: SystemTime::now(),
+            exception: None,
+            cause: None,
+            suppressed: Vec::new(),
+        }
+    }
+
+    /// Returns the thrown value.
+    pub fn value(&self) -> &(dyn Any + Send) {
+        &*self.value
+    }
+
+    /// Returns the thrown value if it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// Returns the name of the thrown value's type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns where the value was thrown.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the name of the thread the value was thrown on, if it had
+    /// one.
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread.as_deref()
+    }
+
+    /// Returns when the value was thrown.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the thrown value as an [`Exception`], if its type implements
+    /// the trait.
+    pub fn exception(&self) -> Option<&dyn Exception> {
+        self.exception.and_then(|cast| cast.view(&*self.value))
+    }
+
+    pub(crate) fn cast(&self) -> Option<Cast> {
+        self.exception
+    }
+
+    /// Returns the payload this value was thrown in response to, if it was
+    /// thrown with `throw_from!`. See [`chain`](crate::chain) to walk the
+    /// whole chain.
+    pub fn cause(&self) -> Option<&(dyn Any + Send)> {
+        self.cause.as_deref()
+    }
+
+    /// Returns the payloads of the panics raised by `finally` blocks while
+    /// this value was propagating, oldest first.
+    pub fn suppressed(&self) -> &[Box<dyn Any + Send>] {
+        &self.suppressed
+    }
+
+    pub(crate) fn suppress(&mut self, payload: Box<dyn Any + Send>) {
+        self.suppressed.push(payload);
+    }
+
+    pub(crate) fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.value.downcast_mut()
+    }
+
+    /// Swaps the thrown value out for `()`.
+    pub(crate) fn take_value(&mut self) -> Box<dyn Any + Send> {
+        std::mem::replace(&mut self.value, Box::new(()))
+    }
+
+    pub(crate) fn set_value(&mut self, value: Box<dyn Any + Send>) {
+        self.value = value;
+    }
+}
+
+impl fmt::Debug for Thrown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Thrown")
+            .field("type_name", &self.type_name)
+            .field("location", &self.location)
+            .field("thread", &self.thread)
+            .field("timestamp", &self.timestamp)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Panics with `value` wrapped in a [`Thrown`] envelope. `exception` views
+/// the value as an [`Exception`] if its type implements the trait, and
+/// `cause` is the payload it was thrown in response to.
+///
+/// Installs the panic hook first, so the value is reported even if it
+/// escapes before any `rsexcept!` block has run.
+#[track_caller]
+pub fn throw<T: Any + Send>(
+    value: T,
+    exception: Option<Cast>,
+    cause: Option<Box<dyn Any + Send>>,
+) -> ! {
+    crate::hook::install_hook();
+    let mut thrown = Thrown::new(value);
+    thrown.exception = exception;
+    thrown.cause = cause;
+    panic::panic_any(thrown)
+}
@@ -0,0 +1,42 @@
+//! Support code for choosing how strictly a try block is checked for unwind
+//! safety.
+//!
+//! `catch_unwind` only accepts closures which are `UnwindSafe`. By default
+//! the try block is passed through [`try_block_must_be_unwind_safe`], whose
+//! bound is [`TryBlockUnwindSafe`] rather than `UnwindSafe` itself, so a
+//! capture that isn't unwind safe gets an error about the try block which
+//! points to `unsafe_unwind try`. `unsafe_unwind try` blocks go through
+//! [`assert_unwind_safe`] instead.
+
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+
+/// Implemented for the closures which run a try block if they are
+/// `UnwindSafe`.
+///
+/// The impl isn't recommended in diagnostics, so if the bound fails, the
+/// compiler reports this trait's message instead of the `UnwindSafe` bound
+/// of some captured type.
+#[diagnostic::on_unimplemented(
+    message = "the try block captures a value which isn't unwind safe",
+    label = "a value captured by this try block isn't unwind safe",
+    note = "a panic could leave a captured `&mut` reference or `RefCell` half-updated",
+    note = "write `unsafe_unwind try` if that state is never observed after a panic"
+)]
+pub trait TryBlockUnwindSafe {}
+
+#[diagnostic::do_not_recommend]
+impl<F: UnwindSafe> TryBlockUnwindSafe for F {}
+
+/// Requires the closure which runs a try block to be `UnwindSafe`.
+///
+/// The bound is checked here, so the closure is passed on in an
+/// `AssertUnwindSafe` and `catch_unwind` doesn't report it a second time.
+pub fn try_block_must_be_unwind_safe<F: TryBlockUnwindSafe>(body: F) -> AssertUnwindSafe<F> {
+    AssertUnwindSafe(body)
+}
+
+/// Asserts that the closure which runs an `unsafe_unwind try` block is
+/// unwind safe.
+pub fn assert_unwind_safe<F>(body: F) -> AssertUnwindSafe<F> {
+    AssertUnwindSafe(body)
+}